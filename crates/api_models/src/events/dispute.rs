@@ -32,3 +32,24 @@ impl ApiEventMetric for DeleteEvidenceRequest {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit_evidence_request_event_type_contains_dispute_id() {
+        let request = SubmitEvidenceRequest {
+            dispute_id: "dp_123".to_string(),
+            receipt: Some("file_123".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            request.get_api_event_type(),
+            Some(ApiEventsType::Dispute {
+                dispute_id: "dp_123".to_string()
+            })
+        );
+    }
+}
@@ -542,6 +542,52 @@ pub enum Currency {
     ZMW,
 }
 
+/// Rounding applied when a float amount in major unit is converted to the lowest denomination
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AmountRoundingMode {
+    /// Ties are rounded away from zero
+    #[default]
+    HalfUp,
+    /// Ties are rounded to the nearest even number (bankers' rounding)
+    HalfEven,
+}
+
+impl AmountRoundingMode {
+    /// Number of decimal places of a lowest denomination amount that are kept before rounding,
+    /// to discard artifacts of float arithmetic like `1.005 * 100.0 = 100.49999999999999`
+    const PRECISION: f64 = 1_000_000.0;
+
+    /// Round an amount in the lowest denomination to a whole number
+    pub fn round(self, amount: f64) -> f64 {
+        let amount = (amount * Self::PRECISION).round() / Self::PRECISION;
+        match self {
+            Self::HalfUp => amount.round(),
+            Self::HalfEven => {
+                let rounded = amount.round();
+                let is_tie = ((amount - amount.trunc()).abs() - 0.5).abs() < f64::EPSILON;
+                if is_tie && rounded % 2.0 != 0.0 {
+                    rounded - amount.signum()
+                } else {
+                    rounded
+                }
+            }
+        }
+    }
+}
+
 impl Currency {
     /// Convert the amount to its base denomination based on Currency and return String
     pub fn to_currency_base_unit(&self, amount: i64) -> Result<String, TryFromIntError> {
@@ -552,27 +598,24 @@ impl Currency {
     /// Convert the amount to its base denomination based on Currency and return f64
     pub fn to_currency_base_unit_asf64(&self, amount: i64) -> Result<f64, TryFromIntError> {
         let amount_f64: f64 = u32::try_from(amount)?.into();
-        let amount = if self.is_zero_decimal_currency() {
-            amount_f64
-        } else if self.is_three_decimal_currency() {
-            amount_f64 / 1000.00
-        } else {
-            amount_f64 / 100.00
-        };
-        Ok(amount)
+        Ok(amount_f64 / self.minor_units_per_major_unit())
     }
 
     ///Convert the higher decimal amount to its base absolute units
     pub fn to_currency_lower_unit(&self, amount: String) -> Result<String, ParseFloatError> {
+        self.to_currency_lower_unit_with_rounding(amount, AmountRoundingMode::default())
+    }
+
+    ///Convert the higher decimal amount to its base absolute units, rounding the result with the given rounding mode
+    pub fn to_currency_lower_unit_with_rounding(
+        &self,
+        amount: String,
+        rounding_mode: AmountRoundingMode,
+    ) -> Result<String, ParseFloatError> {
         let amount_f64 = amount.parse::<f64>()?;
-        let amount_string = if self.is_zero_decimal_currency() {
-            amount_f64
-        } else if self.is_three_decimal_currency() {
-            amount_f64 * 1000.00
-        } else {
-            amount_f64 * 100.00
-        };
-        Ok(amount_string.to_string())
+        Ok(rounding_mode
+            .round(amount_f64 * self.minor_units_per_major_unit())
+            .to_string())
     }
 
     /// Convert the amount to its base denomination based on Currency and check for zero decimal currency and return String
@@ -590,6 +633,25 @@ impl Currency {
         }
     }
 
+    /// Format an amount in the lowest denomination along with the currency code for display.
+    /// Eg: `1000` in `USD` is formatted as `"10.00 USD"` and `1000` in `JPY` as `"1000 JPY"`
+    pub fn format_amount(&self, amount: i64) -> String {
+        let divisor = 10_u64.pow(self.currency_exponent());
+        let decimal_places = usize::from(self.number_of_digits_after_decimal_point());
+        let sign = if amount.is_negative() { "-" } else { "" };
+        let amount = amount.unsigned_abs();
+        if decimal_places == 0 {
+            format!("{sign}{amount} {self}")
+        } else {
+            format!(
+                "{sign}{}.{:0width$} {self}",
+                amount / divisor,
+                amount % divisor,
+                width = decimal_places
+            )
+        }
+    }
+
     pub fn iso_4217(&self) -> &'static str {
         match *self {
             Self::AED => "784",
@@ -1045,6 +1107,17 @@ impl Currency {
             2
         }
     }
+
+    /// Exponent of the minor unit of the currency, i.e. the number of digits after the decimal
+    /// point, as used in the conversions between the base and the lowest denomination
+    pub fn currency_exponent(self) -> u32 {
+        u32::from(self.number_of_digits_after_decimal_point())
+    }
+
+    /// Number of units of the lowest denomination that make up one unit of the base denomination
+    fn minor_units_per_major_unit(self) -> f64 {
+        f64::from(10_u32.pow(self.currency_exponent()))
+    }
 }
 
 #[derive(
@@ -2720,3 +2793,106 @@ pub enum TokenPurpose {
     AcceptInvite,
     UserInfo,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_format_amount_two_decimal_currency() {
+        assert_eq!(Currency::USD.format_amount(1000), "10.00 USD");
+        assert_eq!(Currency::USD.format_amount(1005), "10.05 USD");
+        assert_eq!(Currency::USD.format_amount(5), "0.05 USD");
+        assert_eq!(Currency::USD.format_amount(-1050), "-10.50 USD");
+    }
+
+    #[test]
+    fn test_format_amount_zero_decimal_currency() {
+        assert_eq!(Currency::JPY.format_amount(1000), "1000 JPY");
+        assert_eq!(Currency::JPY.format_amount(0), "0 JPY");
+    }
+
+    #[test]
+    fn test_format_amount_three_decimal_currency() {
+        assert_eq!(Currency::BHD.format_amount(1000), "1.000 BHD");
+        assert_eq!(Currency::BHD.format_amount(12345), "12.345 BHD");
+        assert_eq!(Currency::BHD.format_amount(7), "0.007 BHD");
+    }
+
+    #[test]
+    fn test_to_currency_lower_unit_rounds_float_artifacts() {
+        let amount = (0.1_f64 + 0.2_f64).to_string();
+        assert_eq!(amount, "0.30000000000000004");
+        assert_eq!(Currency::USD.to_currency_lower_unit(amount).unwrap(), "30");
+        assert_eq!(
+            Currency::USD
+                .to_currency_lower_unit("10.07".to_string())
+                .unwrap(),
+            "1007"
+        );
+        assert_eq!(
+            Currency::USD
+                .to_currency_lower_unit("1.005".to_string())
+                .unwrap(),
+            "101"
+        );
+    }
+
+    #[test]
+    fn test_to_currency_lower_unit_with_half_even_rounding() {
+        let to_lower_unit = |amount: &str, currency: Currency| {
+            currency
+                .to_currency_lower_unit_with_rounding(
+                    amount.to_string(),
+                    AmountRoundingMode::HalfEven,
+                )
+                .unwrap()
+        };
+        assert_eq!(to_lower_unit("1.005", Currency::USD), "100");
+        assert_eq!(to_lower_unit("1.015", Currency::USD), "102");
+        assert_eq!(to_lower_unit("2.5", Currency::JPY), "2");
+        assert_eq!(to_lower_unit("-2.5", Currency::JPY), "-2");
+        assert_eq!(to_lower_unit("0.30000000000000004", Currency::USD), "30");
+    }
+
+    #[test]
+    fn test_amount_rounding_mode_half_up() {
+        assert_eq!(AmountRoundingMode::HalfUp.round(2.5).to_string(), "3");
+        assert_eq!(AmountRoundingMode::HalfUp.round(-2.5).to_string(), "-3");
+        assert_eq!(AmountRoundingMode::HalfUp.round(2.4999).to_string(), "2");
+    }
+
+    #[test]
+    fn test_currency_exponent() {
+        assert_eq!(Currency::JPY.currency_exponent(), 0);
+        assert_eq!(Currency::KRW.currency_exponent(), 0);
+        assert_eq!(Currency::USD.currency_exponent(), 2);
+        assert_eq!(Currency::EUR.currency_exponent(), 2);
+        assert_eq!(Currency::BHD.currency_exponent(), 3);
+        assert_eq!(Currency::KWD.currency_exponent(), 3);
+    }
+
+    #[test]
+    fn test_currency_base_unit_conversions() {
+        assert_eq!(
+            Currency::JPY.to_currency_base_unit(1000).unwrap(),
+            "1000.00"
+        );
+        assert_eq!(Currency::USD.to_currency_base_unit(1005).unwrap(), "10.05");
+        assert_eq!(Currency::BHD.to_currency_base_unit(1500).unwrap(), "1.50");
+        assert_eq!(
+            Currency::JPY
+                .to_currency_lower_unit("1000".to_string())
+                .unwrap(),
+            "1000"
+        );
+        assert_eq!(
+            Currency::BHD
+                .to_currency_lower_unit("1.5".to_string())
+                .unwrap(),
+            "1500"
+        );
+    }
+}
@@ -266,11 +266,49 @@ pub mod iso8601custom {
     }
 }
 
+/// Serialize an amount in minor unit as a string, so that JavaScript clients do not lose
+/// precision for values beyond 2^53. Both string and number forms are accepted when deserializing.
+pub mod minor_unit_as_string {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(i64),
+    }
+
+    /// Serialize the amount as a decimal string
+    pub fn serialize<S>(amount: &i64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(amount)
+    }
+
+    /// Deserialize the amount from either a decimal string or a number
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<i64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match StringOrNumber::deserialize(deserializer)? {
+            StringOrNumber::String(amount) => amount.trim().parse().map_err(D::Error::custom),
+            StringOrNumber::Number(amount) => Ok(amount),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
     use serde_json::json;
 
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Amount {
+        #[serde(with = "crate::custom_serde::minor_unit_as_string")]
+        amount: i64,
+    }
+
     #[test]
     fn test_leap_second_parse() {
         #[derive(Serialize, Deserialize)]
@@ -283,4 +321,27 @@ mod tests {
 
         assert!(deser.is_ok())
     }
+
+    #[test]
+    fn test_minor_unit_as_string_round_trip() {
+        let amount = Amount {
+            amount: 9_007_199_254_740_993,
+        };
+
+        let serialized = serde_json::to_value(&amount);
+        assert_eq!(
+            serialized.as_ref().ok(),
+            Some(&json!({"amount": "9007199254740993"}))
+        );
+
+        let deserialized = serialized.and_then(serde_json::from_value::<Amount>);
+        assert_eq!(deserialized.ok(), Some(amount));
+    }
+
+    #[test]
+    fn test_minor_unit_as_string_accepts_number() {
+        let deserialized = serde_json::from_value::<Amount>(json!({"amount": 1000}));
+
+        assert_eq!(deserialized.ok(), Some(Amount { amount: 1000 }));
+    }
 }
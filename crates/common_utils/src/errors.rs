@@ -32,6 +32,9 @@ pub enum ParsingError {
     /// Failed to parse phone number
     #[error("Failed to parse phone number")]
     PhoneNumberParsingError,
+    /// Integer overflow while computing a value
+    #[error("Integer overflow while computing {0}")]
+    IntegerOverflow(&'static str),
 }
 
 /// Validation errors.
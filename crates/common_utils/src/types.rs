@@ -14,7 +14,7 @@ use serde::{de::Visitor, Deserialize, Deserializer};
 
 use crate::{
     consts,
-    errors::{CustomResult, ParsingError, PercentageError},
+    errors::{CustomResult, ParsingError, PercentageError, ValidationError},
 };
 /// Represents Percentage Value between 0 and 100 both inclusive
 #[derive(Clone, Default, Debug, PartialEq, serde::Serialize)]
@@ -70,6 +70,31 @@ impl<const PRECISION: u8> Percentage<PRECISION> {
             Ok(result)
         }
     }
+
+    /// apply the percentage to amount and round the exact result half away from zero, computed in
+    /// integer arithmetic instead of ceiling a float result
+    pub fn apply_and_round_result(&self, amount: i64) -> CustomResult<i64, PercentageError> {
+        let precision_factor = 10_i128.pow(u32::from(PRECISION));
+        // the percentage is validated to have at most `PRECISION` decimal places
+        #[allow(clippy::as_conversions)]
+        let scaled_percentage =
+            (f64::from(self.percentage) * precision_factor as f64).round() as i128;
+        let numerator = i128::from(amount) * scaled_percentage;
+        let denominator = 100 * precision_factor;
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+        let result = if 2 * remainder.abs() >= denominator {
+            quotient + numerator.signum()
+        } else {
+            quotient
+        };
+        i64::try_from(result)
+            .change_context(PercentageError::UnableToApplyPercentage {
+                percentage: self.percentage,
+                amount,
+            })
+            .attach_printable("Percentage applied to the amount overflowed")
+    }
     fn is_valid_string_value(value: &str) -> CustomResult<bool, PercentageError> {
         let float_value = Self::is_valid_float_string(value)?;
         Ok(Self::is_valid_range(float_value) && Self::is_valid_precision_length(value))
@@ -156,6 +181,94 @@ pub enum Surcharge {
     Rate(Percentage<{ consts::SURCHARGE_PERCENTAGE_PRECISION_LENGTH }>),
 }
 
+/// Fee made up of an optional percentage of the base amount and an optional fixed amount, such as
+/// `2% + 30`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeeSchedule<const PRECISION: u8> {
+    /// Percentage of the base amount charged as fee
+    pub percentage: Option<Percentage<PRECISION>>,
+    /// Fixed amount charged as fee, in the lowest denomination of the currency
+    pub fixed_amount: Option<i64>,
+}
+
+impl<const PRECISION: u8> FeeSchedule<PRECISION> {
+    /// compute the fee for the base amount, by summing the ceiled percentage of the base amount and
+    /// the fixed amount
+    pub fn apply(&self, base_amount: i64) -> CustomResult<i64, PercentageError> {
+        let percentage_amount = self
+            .percentage
+            .as_ref()
+            .map(|percentage| percentage.apply_and_ceil_result(base_amount))
+            .transpose()?
+            .unwrap_or(0);
+        percentage_amount
+            .checked_add(self.fixed_amount.unwrap_or(0))
+            .ok_or_else(|| {
+                report!(PercentageError::UnableToApplyPercentage {
+                    percentage: self
+                        .percentage
+                        .as_ref()
+                        .map(Percentage::get_percentage)
+                        .unwrap_or_default(),
+                    amount: base_amount,
+                })
+            })
+            .attach_printable("Fee amount overflowed while adding the fixed amount")
+    }
+}
+
+impl From<Surcharge> for FeeSchedule<{ consts::SURCHARGE_PERCENTAGE_PRECISION_LENGTH }> {
+    fn from(surcharge: Surcharge) -> Self {
+        match surcharge {
+            Surcharge::Fixed(fixed_amount) => Self {
+                percentage: None,
+                fixed_amount: Some(fixed_amount),
+            },
+            Surcharge::Rate(percentage) => Self {
+                percentage: Some(percentage),
+                fixed_amount: None,
+            },
+        }
+    }
+}
+
+/// Split an amount in the lowest denomination into `parts` amounts that sum up to the original
+/// amount. The remainder is distributed one unit each to the first entries, so `100` split into
+/// `3` parts yields `[34, 33, 33]`.
+pub fn split_amount_evenly(amount: i64, parts: u32) -> CustomResult<Vec<i64>, ValidationError> {
+    if parts == 0 {
+        return Err(report!(ValidationError::IncorrectValueProvided {
+            field_name: "parts"
+        }))
+        .attach_printable("Cannot split an amount into zero parts");
+    }
+    let divisor = i64::from(parts);
+    let quotient = amount.div_euclid(divisor);
+    let remainder = amount.rem_euclid(divisor);
+    Ok((0..divisor)
+        .map(|index| {
+            if index < remainder {
+                quotient + 1
+            } else {
+                quotient
+            }
+        })
+        .collect())
+}
+
+/// Signed difference `amount - other` of two amounts in the lowest denomination, such as
+/// captured minus refunded. A negative result is returned as is, only an `i64` overflow errors.
+pub fn amount_difference(amount: i64, other: i64) -> CustomResult<i64, ValidationError> {
+    amount
+        .checked_sub(other)
+        .ok_or_else(|| {
+            report!(ValidationError::IncorrectValueProvided {
+                field_name: "amount"
+            })
+        })
+        .attach_printable("Amount difference overflowed")
+}
+
 /// This struct lets us represent a semantic version type
 #[derive(Debug, Clone, PartialEq, Eq, FromSqlRow, AsExpression, Ord, PartialOrd)]
 #[diesel(sql_type = Jsonb)]
@@ -0,0 +1,62 @@
+#![allow(clippy::unwrap_used)]
+use common_utils::{
+    errors::ValidationError,
+    types::{amount_difference, split_amount_evenly},
+};
+
+#[test]
+fn split_amount_with_remainder() {
+    let split_amounts = split_amount_evenly(100, 3).unwrap();
+    assert_eq!(split_amounts, vec![34, 33, 33]);
+    assert_eq!(split_amounts.iter().sum::<i64>(), 100);
+}
+
+#[test]
+fn split_amount_without_remainder() {
+    let split_amounts = split_amount_evenly(100, 4).unwrap();
+    assert_eq!(split_amounts, vec![25, 25, 25, 25]);
+}
+
+#[test]
+fn split_amount_smaller_than_parts() {
+    let split_amounts = split_amount_evenly(2, 3).unwrap();
+    assert_eq!(split_amounts, vec![1, 1, 0]);
+    assert_eq!(split_amounts.iter().sum::<i64>(), 2);
+}
+
+#[test]
+fn split_amount_into_zero_parts() {
+    let result = split_amount_evenly(100, 0);
+    assert!(matches!(
+        result.unwrap_err().current_context(),
+        ValidationError::IncorrectValueProvided {
+            field_name: "parts"
+        }
+    ));
+}
+
+#[test]
+fn amount_difference_positive() {
+    assert_eq!(amount_difference(1000, 400).unwrap(), 600);
+}
+
+#[test]
+fn amount_difference_zero() {
+    assert_eq!(amount_difference(1000, 1000).unwrap(), 0);
+}
+
+#[test]
+fn amount_difference_negative() {
+    assert_eq!(amount_difference(400, 1000).unwrap(), -600);
+}
+
+#[test]
+fn amount_difference_overflow() {
+    let result = amount_difference(i64::MIN, 1);
+    assert!(matches!(
+        result.unwrap_err().current_context(),
+        ValidationError::IncorrectValueProvided {
+            field_name: "amount"
+        }
+    ));
+}
@@ -1,5 +1,8 @@
 #![allow(clippy::panic_in_result_fn)]
-use common_utils::{errors::PercentageError, types::Percentage};
+use common_utils::{
+    errors::PercentageError,
+    types::{FeeSchedule, Percentage},
+};
 const PRECISION_2: u8 = 2;
 const PRECISION_0: u8 = 0;
 
@@ -198,3 +201,87 @@ fn deserialization_test_err() -> Result<(), Box<dyn std::error::Error + Send + S
     }
     Ok(())
 }
+
+#[test]
+fn apply_and_round_result() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let percentage = Percentage::<PRECISION_2>::from_string("1.1".to_string())?;
+
+    // 1.1% of 1000 is exactly 11, while ceiling the float result of the multiplication adds one
+    assert_eq!(percentage.apply_and_ceil_result(1000)?, 12);
+    assert_eq!(percentage.apply_and_round_result(1000)?, 11);
+
+    // 2.5% of 1005 is 25.125 and 2.5% of 1020 is 25.5, which are rounded half away from zero
+    let percentage = Percentage::<PRECISION_2>::from_string("2.5".to_string())?;
+    assert_eq!(percentage.apply_and_round_result(1005)?, 25);
+    assert_eq!(percentage.apply_and_round_result(1020)?, 26);
+    assert_eq!(percentage.apply_and_round_result(-1020)?, -26);
+    Ok(())
+}
+
+#[test]
+fn apply_and_round_result_rounds_instead_of_ceiling(
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let percentage = Percentage::<PRECISION_2>::from_string("2.5".to_string())?;
+
+    // 2.5% of 105 is 2.625 and 2.5% of 1050 is 26.25, both of which are ceiled up
+    assert_eq!(percentage.apply_and_round_result(105)?, 3);
+    assert_eq!(percentage.apply_and_ceil_result(105)?, 3);
+    assert_eq!(percentage.apply_and_round_result(1050)?, 26);
+    assert_eq!(percentage.apply_and_ceil_result(1050)?, 27);
+    Ok(())
+}
+
+#[test]
+fn apply_and_round_result_large_amount() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let percentage = Percentage::<PRECISION_2>::from_string("100".to_string())?;
+    assert_eq!(percentage.apply_and_round_result(i64::MAX)?, i64::MAX);
+    Ok(())
+}
+
+#[test]
+fn fee_schedule_with_percentage_only() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let fee_schedule = FeeSchedule::<PRECISION_2> {
+        percentage: Some(Percentage::from_string("2.5".to_string())?),
+        fixed_amount: None,
+    };
+    assert_eq!(fee_schedule.apply(1000)?, 25);
+    Ok(())
+}
+
+#[test]
+fn fee_schedule_with_fixed_amount_only() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let fee_schedule = FeeSchedule::<PRECISION_2> {
+        percentage: None,
+        fixed_amount: Some(30),
+    };
+    assert_eq!(fee_schedule.apply(1000)?, 30);
+    assert_eq!(FeeSchedule::<PRECISION_2>::default().apply(1000)?, 0);
+    Ok(())
+}
+
+#[test]
+fn fee_schedule_with_percentage_and_fixed_amount(
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let fee_schedule = FeeSchedule::<PRECISION_2> {
+        percentage: Some(Percentage::from_string("2".to_string())?),
+        fixed_amount: Some(30),
+    };
+    assert_eq!(fee_schedule.apply(1000)?, 50);
+
+    let fee_schedule = FeeSchedule::<PRECISION_2> {
+        percentage: Some(Percentage::from_string("2".to_string())?),
+        fixed_amount: Some(i64::MAX),
+    };
+    let fee = fee_schedule.apply(1000);
+    assert!(fee.is_err());
+    if let Err(err) = fee {
+        assert_eq!(
+            *err.current_context(),
+            PercentageError::UnableToApplyPercentage {
+                percentage: 2.0,
+                amount: 1000,
+            }
+        )
+    }
+    Ok(())
+}
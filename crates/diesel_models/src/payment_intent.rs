@@ -549,4 +549,83 @@ mod tests {
 
         assert!(deserialized_payment_intent.is_ok());
     }
+
+    #[test]
+    fn test_metadata_update_touches_only_metadata() {
+        use masking::PeekInterface;
+
+        let metadata = masking::Secret::new(serde_json::json!({ "tag": "vip" }));
+
+        // Destructured exhaustively, so that a new field has to be accounted for here
+        let super::PaymentIntentUpdateInternal {
+            amount,
+            currency,
+            status,
+            amount_captured,
+            customer_id,
+            return_url,
+            setup_future_usage,
+            off_session,
+            metadata: updated_metadata,
+            billing_address_id,
+            shipping_address_id,
+            modified_at,
+            active_attempt_id,
+            business_country,
+            business_label,
+            description,
+            statement_descriptor_name,
+            statement_descriptor_suffix,
+            order_details,
+            attempt_count,
+            profile_id,
+            merchant_decision,
+            payment_confirm_source,
+            updated_by,
+            surcharge_applicable,
+            incremental_authorization_allowed,
+            authorization_count,
+            session_expiry,
+            fingerprint_id,
+            request_external_three_ds_authentication,
+        } = super::PaymentIntentUpdateInternal::from(super::PaymentIntentUpdate::MetadataUpdate {
+            metadata,
+            updated_by: "redis_kv".to_string(),
+        });
+
+        assert_eq!(
+            updated_metadata.map(|metadata| metadata.peek().clone()),
+            Some(serde_json::json!({ "tag": "vip" }))
+        );
+        assert!(modified_at.is_some());
+        assert_eq!(updated_by, "redis_kv");
+
+        assert!(amount.is_none());
+        assert!(currency.is_none());
+        assert!(status.is_none());
+        assert!(amount_captured.is_none());
+        assert!(customer_id.is_none());
+        assert!(return_url.is_none());
+        assert!(setup_future_usage.is_none());
+        assert!(off_session.is_none());
+        assert!(billing_address_id.is_none());
+        assert!(shipping_address_id.is_none());
+        assert!(active_attempt_id.is_none());
+        assert!(business_country.is_none());
+        assert!(business_label.is_none());
+        assert!(description.is_none());
+        assert!(statement_descriptor_name.is_none());
+        assert!(statement_descriptor_suffix.is_none());
+        assert!(order_details.is_none());
+        assert!(attempt_count.is_none());
+        assert!(profile_id.is_none());
+        assert!(merchant_decision.is_none());
+        assert!(payment_confirm_source.is_none());
+        assert!(surcharge_applicable.is_none());
+        assert!(incremental_authorization_allowed.is_none());
+        assert!(authorization_count.is_none());
+        assert!(session_expiry.is_none());
+        assert!(fingerprint_id.is_none());
+        assert!(request_external_three_ds_authentication.is_none());
+    }
 }
@@ -1,6 +1,10 @@
-use common_utils::pii;
+use common_utils::{
+    errors::{CustomResult, ParsingError},
+    ext_traits::ValueExt,
+    pii,
+};
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
-use masking::Secret;
+use masking::{PeekInterface, Secret};
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
@@ -42,6 +46,59 @@ pub struct PaymentMethod {
     pub client_secret: Option<String>,
 }
 
+impl PaymentMethod {
+    /// Parse the payment method metadata into the given type, returning `None` if metadata is absent
+    pub fn parse_metadata<T>(&self) -> CustomResult<Option<T>, ParsingError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.metadata
+            .as_ref()
+            .map(|metadata| {
+                metadata
+                    .peek()
+                    .clone()
+                    .parse_value(std::any::type_name::<T>())
+            })
+            .transpose()
+    }
+
+    /// Build a representation of the payment method that is safe to be included in responses,
+    /// leaving out encrypted data, tokens and other sensitive fields
+    pub fn to_masked_response(&self) -> MaskedPaymentMethod {
+        MaskedPaymentMethod {
+            customer_id: self.customer_id.clone(),
+            merchant_id: self.merchant_id.clone(),
+            payment_method_id: self.payment_method_id.clone(),
+            payment_method: self.payment_method,
+            payment_method_type: self.payment_method_type,
+            payment_method_issuer: self.payment_method_issuer.clone(),
+            scheme: self.scheme.clone(),
+            issuer_country: self.issuer_country.clone(),
+            status: self.status,
+            created_at: self.created_at,
+            last_modified: self.last_modified,
+            last_used_at: self.last_used_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MaskedPaymentMethod {
+    pub customer_id: String,
+    pub merchant_id: String,
+    pub payment_method_id: String,
+    pub payment_method: Option<storage_enums::PaymentMethod>,
+    pub payment_method_type: Option<storage_enums::PaymentMethodType>,
+    pub payment_method_issuer: Option<String>,
+    pub scheme: Option<String>,
+    pub issuer_country: Option<String>,
+    pub status: storage_enums::PaymentMethodStatus,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified: PrimitiveDateTime,
+    pub last_used_at: PrimitiveDateTime,
+}
+
 #[derive(
     Clone, Debug, Eq, PartialEq, Insertable, router_derive::DebugAsDisplay, Serialize, Deserialize,
 )]
@@ -320,3 +377,74 @@ impl From<&PaymentMethodNew> for PaymentMethod {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use masking::Secret;
+
+    use super::{PaymentMethod, PaymentMethodNew};
+    use crate::encryption::Encryption;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct TestMetadata {
+        nickname: String,
+    }
+
+    fn get_payment_method(metadata: Option<serde_json::Value>) -> PaymentMethod {
+        PaymentMethod::from(&PaymentMethodNew {
+            metadata: metadata.map(Secret::new),
+            ..PaymentMethodNew::default()
+        })
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let payment_method =
+            get_payment_method(Some(serde_json::json!({ "nickname": "Travel card" })));
+
+        let metadata = payment_method.parse_metadata::<TestMetadata>().unwrap();
+
+        assert_eq!(
+            metadata,
+            Some(TestMetadata {
+                nickname: "Travel card".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_metadata_absent() {
+        let payment_method = get_payment_method(None);
+
+        let metadata = payment_method.parse_metadata::<TestMetadata>().unwrap();
+
+        assert_eq!(metadata, None);
+    }
+
+    #[test]
+    fn test_parse_metadata_malformed() {
+        let payment_method = get_payment_method(Some(serde_json::json!({ "nickname": 42 })));
+
+        let result = payment_method.parse_metadata::<TestMetadata>();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_masked_response_omits_encrypted_fields() {
+        let payment_method = PaymentMethod::from(&PaymentMethodNew {
+            payment_method_id: "pm_123".to_string(),
+            payment_method_data: Some(Encryption::new(Secret::new(vec![1, 2, 3]))),
+            locker_id: Some("locker_123".to_string()),
+            ..PaymentMethodNew::default()
+        });
+
+        let masked = serde_json::to_value(payment_method.to_masked_response()).unwrap();
+
+        assert_eq!(masked["payment_method_id"], "pm_123");
+        assert!(masked.get("payment_method_data").is_none());
+        assert!(masked.get("locker_id").is_none());
+        assert!(masked.get("metadata").is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use common_utils::pii;
 use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
 
 use super::generics;
@@ -41,6 +42,23 @@ impl PaymentIntent {
         }
     }
 
+    /// Update only the metadata of the payment intent, along with `modified_at` and `updated_by`
+    pub async fn update_metadata(
+        self,
+        conn: &PgPooledConn,
+        metadata: pii::SecretSerdeValue,
+        updated_by: String,
+    ) -> StorageResult<Self> {
+        self.update(
+            conn,
+            PaymentIntentUpdate::MetadataUpdate {
+                metadata,
+                updated_by,
+            },
+        )
+        .await
+    }
+
     pub async fn find_by_payment_id_merchant_id(
         conn: &PgPooledConn,
         payment_id: &str,
@@ -34,3 +34,124 @@ impl<T: ForeignIDRef> RemoteStorageObject<T> {
         }
     }
 }
+
+/// Resolves all the `ForeignID` variants in `items` with a single call to `batch_fetch`,
+/// preserving the order of `items`. `Object` variants are passed through untouched, and ids
+/// which are not returned by `batch_fetch` are left as `ForeignID`.
+pub async fn resolve_all<T, E, F, Fut>(
+    items: Vec<RemoteStorageObject<T>>,
+    batch_fetch: F,
+) -> Result<Vec<RemoteStorageObject<T>>, E>
+where
+    T: ForeignIDRef + Clone,
+    F: FnOnce(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<T>, E>>,
+{
+    let mut foreign_ids = Vec::new();
+    for item in &items {
+        if let RemoteStorageObject::ForeignID(id) = item {
+            if !foreign_ids.contains(id) {
+                foreign_ids.push(id.clone());
+            }
+        }
+    }
+
+    if foreign_ids.is_empty() {
+        return Ok(items);
+    }
+
+    let fetched = batch_fetch(foreign_ids)
+        .await?
+        .into_iter()
+        .map(|object| (object.foreign_id(), object))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    Ok(items
+        .into_iter()
+        .map(|item| match item {
+            RemoteStorageObject::ForeignID(id) => fetched
+                .get(&id)
+                .cloned()
+                .map(RemoteStorageObject::Object)
+                .unwrap_or(RemoteStorageObject::ForeignID(id)),
+            object @ RemoteStorageObject::Object(_) => object,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    struct Item {
+        id: String,
+        value: u8,
+    }
+
+    impl ForeignIDRef for Item {
+        fn foreign_id(&self) -> String {
+            self.id.clone()
+        }
+    }
+
+    fn item(id: &str, value: u8) -> Item {
+        Item {
+            id: id.to_string(),
+            value,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_all_with_ids_and_inline_objects() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let items = vec![
+            RemoteStorageObject::ForeignID("a".to_string()),
+            RemoteStorageObject::Object(item("b", 2)),
+            RemoteStorageObject::ForeignID("c".to_string()),
+            RemoteStorageObject::ForeignID("a".to_string()),
+            RemoteStorageObject::ForeignID("missing".to_string()),
+        ];
+
+        let batch_calls = Arc::clone(&calls);
+        let resolved = resolve_all(items, |ids| async move {
+            batch_calls.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(ids, vec!["a", "c", "missing"]);
+            Ok::<_, ()>(vec![item("c", 3), item("a", 1)])
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            resolved,
+            vec![
+                RemoteStorageObject::Object(item("a", 1)),
+                RemoteStorageObject::Object(item("b", 2)),
+                RemoteStorageObject::Object(item("c", 3)),
+                RemoteStorageObject::Object(item("a", 1)),
+                RemoteStorageObject::ForeignID("missing".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_all_skips_fetch_for_inline_objects() {
+        let items = vec![RemoteStorageObject::Object(item("a", 1))];
+
+        let resolved = resolve_all(items.clone(), |_| async {
+            Err::<Vec<Item>, _>("batch fetch should not be called")
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(resolved, items);
+    }
+}
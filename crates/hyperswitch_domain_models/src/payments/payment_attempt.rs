@@ -1,5 +1,7 @@
 use api_models::enums::Connector;
 use common_enums as storage_enums;
+use common_utils::errors::{CustomResult, ValidationError};
+use error_stack::{report, ResultExt};
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
@@ -252,15 +254,34 @@ pub struct PaymentAttemptNew {
 }
 
 impl PaymentAttemptNew {
-    /// returns amount + surcharge_amount + tax_amount
-    pub fn calculate_net_amount(&self) -> i64 {
-        self.amount + self.surcharge_amount.unwrap_or(0) + self.tax_amount.unwrap_or(0)
+    /// returns amount + surcharge_amount + tax_amount, failing if the sum overflows
+    pub fn calculate_net_amount(&self) -> CustomResult<i64, ValidationError> {
+        self.amount
+            .checked_add(self.surcharge_amount.unwrap_or(0))
+            .and_then(|amount| amount.checked_add(self.tax_amount.unwrap_or(0)))
+            .ok_or_else(|| {
+                report!(ValidationError::IncorrectValueProvided {
+                    field_name: "net_amount"
+                })
+            })
+            .attach_printable_lazy(|| {
+                format!(
+                    "Net amount overflowed for amount {}, surcharge amount {:?} and tax amount {:?}",
+                    self.amount, self.surcharge_amount, self.tax_amount
+                )
+            })
+    }
+
+    /// Updates `net_amount` from the amount, surcharge amount and tax amount of the attempt
+    pub fn compute_net_amount(&mut self) -> CustomResult<(), ValidationError> {
+        self.net_amount = self.calculate_net_amount()?;
+        Ok(())
     }
 
-    pub fn populate_derived_fields(self) -> Self {
+    pub fn populate_derived_fields(self) -> CustomResult<Self, ValidationError> {
         let mut payment_attempt_new = self;
-        payment_attempt_new.net_amount = payment_attempt_new.calculate_net_amount();
-        payment_attempt_new
+        payment_attempt_new.compute_net_amount()?;
+        Ok(payment_attempt_new)
     }
 }
 
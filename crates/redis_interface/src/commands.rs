@@ -6,7 +6,7 @@
 //!
 //!
 
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug, time::Duration};
 
 use common_utils::{
     errors::CustomResult,
@@ -15,21 +15,344 @@ use common_utils::{
 };
 use error_stack::{report, ResultExt};
 use fred::{
-    interfaces::{HashesInterface, KeysInterface, SetsInterface, StreamsInterface},
+    clients::{RedisClient, Transaction},
+    interfaces::{
+        ClientLike, HashesInterface, KeysInterface, ListInterface, LuaInterface, PubsubInterface,
+        SetsInterface, SortedSetsInterface, StreamsInterface, TransactionInterface,
+    },
     prelude::RedisErrorKind,
     types::{
-        Expiration, FromRedis, MultipleIDs, MultipleKeys, MultipleOrderedPairs, MultipleStrings,
-        MultipleValues, RedisKey, RedisMap, RedisValue, Scanner, SetOptions, XCap, XReadResponse,
+        ClusterHash, CustomCommand, Expiration, FromRedis, MultipleIDs, MultipleKeys,
+        MultipleOrderedPairs, MultipleStrings, MultipleValues, Options, RedisKey, RedisMap,
+        RedisValue, Scanner, SetOptions, XCap, XReadResponse,
     },
 };
 use futures::StreamExt;
 use router_env::{instrument, logger, tracing};
+use time::PrimitiveDateTime;
 
 use crate::{
     errors,
-    types::{DelReply, HsetnxReply, MsetnxReply, RedisEntryId, SaddReply, SetnxReply},
+    types::{
+        CircuitState, DelReply, HsetnxReply, MsetnxReply, PendingSummary, RedisEntryId, SaddReply,
+        SetnxReply,
+    },
 };
 
+/// Await the command future, recording its latency against the command name when the
+/// `command_latency_metrics` feature is enabled
+#[cfg(feature = "command_latency_metrics")]
+macro_rules! timed {
+    ($command:literal, $future:expr) => {
+        command_latency::record($command, $future).await
+    };
+}
+
+#[cfg(not(feature = "command_latency_metrics"))]
+macro_rules! timed {
+    ($command:literal, $future:expr) => {
+        $future.await
+    };
+}
+
+#[cfg(feature = "command_latency_metrics")]
+mod command_latency {
+    use std::{future::Future, time::Instant};
+
+    use router_env::{
+        global_meter, histogram_metric, metrics_context, once_cell, opentelemetry::KeyValue,
+    };
+
+    metrics_context!(CONTEXT);
+    global_meter!(GLOBAL_METER, "REDIS_INTERFACE");
+    histogram_metric!(REDIS_COMMAND_LATENCY, GLOBAL_METER); // Time in (s) seconds
+
+    #[cfg(test)]
+    thread_local! {
+        pub(super) static OBSERVATIONS: std::cell::RefCell<Vec<&'static str>> =
+            std::cell::RefCell::new(Vec::new());
+    }
+
+    pub(super) async fn record<F: Future>(command: &'static str, future: F) -> F::Output {
+        let start = Instant::now();
+        let output = future.await;
+        REDIS_COMMAND_LATENCY.record(
+            &CONTEXT,
+            start.elapsed().as_secs_f64(),
+            &[KeyValue::new("command", command)],
+        );
+        #[cfg(test)]
+        OBSERVATIONS.with(|observations| observations.borrow_mut().push(command));
+        output
+    }
+}
+
+/// Whether the error is a `MOVED` or `ASK` redirection, which a standalone connection receives
+/// when it is pointed at a node of a Redis cluster
+fn is_cluster_redirect(err: &fred::error::RedisError) -> bool {
+    let details = err.details().trim_start();
+    details.starts_with("MOVED ") || details.starts_with("ASK ")
+}
+
+/// Convert the error of a command into the given context, unless the error is a cluster
+/// redirection, which is reported as [`errors::RedisError::ClusterRedirectUnexpected`]
+fn command_error(
+    err: fred::error::RedisError,
+    context: errors::RedisError,
+) -> error_stack::Report<errors::RedisError> {
+    if is_cluster_redirect(&err) {
+        report!(err)
+            .change_context(errors::RedisError::ClusterRedirectUnexpected)
+            .attach_printable("Set `cluster_enabled` to connect to a Redis cluster")
+    } else {
+        report!(err).change_context(context)
+    }
+}
+
+trait CommandResultExt<T> {
+    /// Same as `change_context`, with cluster redirections reported as
+    /// [`errors::RedisError::ClusterRedirectUnexpected`]
+    fn change_command_context(
+        self,
+        context: errors::RedisError,
+    ) -> CustomResult<T, errors::RedisError>;
+}
+
+impl<T> CommandResultExt<T> for Result<T, fred::error::RedisError> {
+    fn change_command_context(
+        self,
+        context: errors::RedisError,
+    ) -> CustomResult<T, errors::RedisError> {
+        self.map_err(|err| command_error(err, context))
+    }
+}
+
+/// Increments the counter at `KEYS[1]`, resetting it to `0` once it reaches `ARGV[1]`
+const WRAPPING_INCREMENT_SCRIPT: &str = r#"
+local value = redis.call("INCR", KEYS[1])
+if value >= tonumber(ARGV[1]) then
+    redis.call("SET", KEYS[1], 0)
+    return 0
+end
+return value
+"#;
+
+/// Evicts the hits older than the window from the sorted set at `KEYS[1]` and records a new hit
+/// if fewer than the limit remain, all in a single round trip so that concurrent callers cannot
+/// interleave. Returns `1` if the hit was recorded and `0` otherwise.
+///
+/// `ARGV`: window start score, current score, limit, member of the hit, TTL of the set in seconds
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+redis.call("ZREMRANGEBYSCORE", KEYS[1], "-inf", ARGV[1])
+if redis.call("ZCARD", KEYS[1]) >= tonumber(ARGV[3]) then
+    return 0
+end
+redis.call("ZADD", KEYS[1], ARGV[2], ARGV[4])
+redis.call("EXPIRE", KEYS[1], ARGV[5])
+return 1
+"#;
+
+/// Upper bound for per-command timeout overrides
+pub const MAX_COMMAND_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Number of keys scanned and unlinked at a time by `flush_prefix`
+const FLUSH_PREFIX_BATCH_SIZE: u32 = 500;
+
+/// Interval at which [`RedisConnectionPool::get_or_set_with_lock`] polls for the value computed
+/// by the caller holding the lock
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Split the keys into batches of keys hashing to the same slot, since Redis rejects multi-key
+/// commands spanning multiple slots in cluster mode
+fn group_keys_by_slot(keys: Vec<RedisKey>) -> Vec<Vec<RedisKey>> {
+    let mut batches: HashMap<u16, Vec<RedisKey>> = HashMap::new();
+    for key in keys {
+        batches.entry(key.cluster_hash()).or_default().push(key);
+    }
+    batches.into_values().collect()
+}
+
+/// Escapes the characters having a special meaning in glob-style patterns, so that the value is
+/// matched literally
+fn escape_glob_pattern(value: &str) -> String {
+    value
+        .chars()
+        .fold(String::with_capacity(value.len()), |mut escaped, c| {
+            if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            escaped
+        })
+}
+
+/// Converts a map-like reply into a map of strings. Accepts map frames, flat arrays of
+/// alternating fields and values, and arrays of `[field, value]` pairs. Nil replies are read as
+/// an empty map.
+pub(crate) fn redis_map_to_string_map(
+    value: RedisValue,
+) -> CustomResult<HashMap<String, String>, errors::RedisError> {
+    fn into_scalar_string(value: RedisValue) -> CustomResult<String, errors::RedisError> {
+        match value {
+            RedisValue::Array(_) | RedisValue::Map(_) | RedisValue::Null => {
+                Err(report!(errors::RedisError::StreamParseFailed))
+                    .attach_printable("Expected a scalar value in map reply")
+            }
+            value => value
+                .into_string()
+                .ok_or(errors::RedisError::StreamParseFailed)
+                .attach_printable("Map reply value is not convertible to a string"),
+        }
+    }
+
+    match value {
+        RedisValue::Null => Ok(HashMap::new()),
+        RedisValue::Map(map) => map
+            .inner()
+            .into_iter()
+            .map(|(field, value)| {
+                let field = field
+                    .into_string()
+                    .ok_or(errors::RedisError::StreamParseFailed)
+                    .attach_printable("Map reply field is not a valid string")?;
+                Ok((field, into_scalar_string(value)?))
+            })
+            .collect(),
+        RedisValue::Array(values)
+            if values
+                .iter()
+                .all(|value| matches!(value, RedisValue::Array(_))) =>
+        {
+            values
+                .into_iter()
+                .map(|pair| {
+                    let mut pair = pair.into_array().into_iter();
+                    match (pair.next(), pair.next(), pair.next()) {
+                        (Some(field), Some(value), None) => {
+                            Ok((into_scalar_string(field)?, into_scalar_string(value)?))
+                        }
+                        _ => Err(report!(errors::RedisError::StreamParseFailed))
+                            .attach_printable("Expected a field and value pair in map reply"),
+                    }
+                })
+                .collect()
+        }
+        RedisValue::Array(values) => {
+            fp_utils::when(values.len() % 2 != 0, || {
+                Err(report!(errors::RedisError::StreamParseFailed))
+                    .attach_printable("Expected an even number of elements in map reply")
+            })?;
+            let mut values = values.into_iter();
+            let mut map = HashMap::with_capacity(values.len() / 2);
+            while let (Some(field), Some(value)) = (values.next(), values.next()) {
+                map.insert(into_scalar_string(field)?, into_scalar_string(value)?);
+            }
+            Ok(map)
+        }
+        _ => Err(report!(errors::RedisError::StreamParseFailed))
+            .attach_printable("Unexpected map reply"),
+    }
+}
+
+/// Keys holding the circuit breaker state of a connector
+struct CircuitBreakerKeys {
+    /// Sliding window log of the recent failures
+    failures: String,
+    /// Present while the breaker is open
+    open: String,
+    /// Present from the breaker opening until a request succeeds, the breaker is half open when
+    /// this is present without `open`
+    tripped: String,
+}
+
+impl CircuitBreakerKeys {
+    fn new(connector: &str) -> Self {
+        Self {
+            failures: format!("circuit_breaker:{connector}:failures"),
+            open: format!("circuit_breaker:{connector}:open"),
+            tripped: format!("circuit_breaker:{connector}:tripped"),
+        }
+    }
+}
+
+async fn close_dedicated_client(client: RedisClient) {
+    client
+        .quit()
+        .await
+        .map_err(|error| logger::warn!(?error, "Failed to close dedicated Redis connection"))
+        .ok();
+}
+
+fn clamp_command_timeout(timeout: Duration) -> Duration {
+    if timeout > MAX_COMMAND_TIMEOUT {
+        logger::warn!(
+            ?timeout,
+            max_timeout = ?MAX_COMMAND_TIMEOUT,
+            "Command timeout exceeds the maximum allowed, clamping it"
+        );
+        MAX_COMMAND_TIMEOUT
+    } else {
+        timeout
+    }
+}
+
+/// MULTI/EXEC transaction handed to the `build` closure of
+/// [`super::RedisConnectionPool::transaction`], applying the key prefix of the pool to the keys of
+/// the queued commands
+pub struct RedisTransaction {
+    transaction: Transaction,
+    prefix: String,
+    default_ttl: u32,
+}
+
+impl RedisTransaction {
+    /// Prepend the environment tag and the key prefix of the pool to the key
+    pub fn add_prefix(&self, key: &str) -> String {
+        super::prefix_key(&self.prefix, key)
+    }
+
+    /// Queue a `SET` of the key, with the default TTL of the pool
+    pub async fn set_key<V>(&self, key: &str, value: V) -> CustomResult<(), errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+    {
+        self.set_key_with_expiry(key, value, self.default_ttl.into())
+            .await
+    }
+
+    /// Queue a `SET` of the key, expiring after the provided number of seconds
+    pub async fn set_key_with_expiry<V>(
+        &self,
+        key: &str,
+        value: V,
+        seconds: i64,
+    ) -> CustomResult<(), errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+    {
+        self.transaction
+            .set(
+                self.add_prefix(key),
+                value,
+                Some(Expiration::EX(seconds)),
+                None,
+                false,
+            )
+            .await
+            .change_command_context(errors::RedisError::SetExFailed)
+    }
+
+    /// Queue a `DEL` of the key
+    pub async fn delete_key(&self, key: &str) -> CustomResult<(), errors::RedisError> {
+        self.transaction
+            .del(self.add_prefix(key))
+            .await
+            .change_command_context(errors::RedisError::DeleteFailed)
+    }
+}
+
 impl super::RedisConnectionPool {
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_key<V>(&self, key: &str, value: V) -> CustomResult<(), errors::RedisError>
@@ -37,16 +360,18 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisValue> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        self.pool
-            .set(
-                key,
+        self.ensure_writable()?;
+        timed!(
+            "SET",
+            self.pool.set(
+                self.add_prefix(key),
                 value,
                 Some(Expiration::EX(self.config.default_ttl.into())),
                 None,
                 false,
             )
-            .await
-            .change_context(errors::RedisError::SetFailed)
+        )
+        .change_command_context(errors::RedisError::SetFailed)
     }
 
     pub async fn set_key_without_modifying_ttl<V>(
@@ -58,10 +383,18 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisValue> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        self.pool
-            .set(key, value, Some(Expiration::KEEPTTL), None, false)
-            .await
-            .change_context(errors::RedisError::SetFailed)
+        self.ensure_writable()?;
+        timed!(
+            "SET",
+            self.pool.set(
+                self.add_prefix(key),
+                value,
+                Some(Expiration::KEEPTTL),
+                None,
+                false,
+            )
+        )
+        .change_command_context(errors::RedisError::SetFailed)
     }
 
     pub async fn set_multiple_keys_if_not_exist<V>(
@@ -72,10 +405,19 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisMap> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        self.pool
-            .msetnx(value)
-            .await
-            .change_context(errors::RedisError::SetFailed)
+        self.ensure_writable()?;
+        let value: RedisMap = value
+            .try_into()
+            .map_err(Into::<fred::error::RedisError>::into)
+            .change_context(errors::RedisError::SetFailed)?;
+        let value = value
+            .inner()
+            .into_iter()
+            .map(|(key, value)| (self.add_prefix(&key.as_str_lossy()), value))
+            .collect::<HashMap<_, _>>();
+
+        timed!("MSETNX", self.pool.msetnx(value))
+            .change_command_context(errors::RedisError::SetFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -138,20 +480,22 @@ impl super::RedisConnectionPool {
     where
         V: serde::Serialize + Debug,
     {
+        self.ensure_writable()?;
         let serialized = value
             .encode_to_vec()
             .change_context(errors::RedisError::JsonSerializationFailed)?;
 
-        self.pool
-            .set(
-                key,
+        timed!(
+            "SET",
+            self.pool.set(
+                self.add_prefix(key),
                 serialized.as_slice(),
                 Some(Expiration::EX(seconds)),
                 None,
                 false,
             )
-            .await
-            .change_context(errors::RedisError::SetExFailed)
+        )
+        .change_command_context(errors::RedisError::SetExFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -159,10 +503,24 @@ impl super::RedisConnectionPool {
     where
         V: FromRedis + Unpin + Send + 'static,
     {
-        self.pool
-            .get(key)
-            .await
-            .change_context(errors::RedisError::GetFailed)
+        timed!("GET", self.pool.get(self.add_prefix(key)))
+            .change_command_context(errors::RedisError::GetFailed)
+    }
+
+    /// Get the value of the key from a replica node if `prefer_replica_reads` is enabled, falling
+    /// back to the primary if no replica is available. Since replication is asynchronous, the value
+    /// may be stale and this should not be used for reads that must observe the latest write.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_key_from_replica<V>(&self, key: &str) -> CustomResult<V, errors::RedisError>
+    where
+        V: FromRedis + Unpin + Send + 'static,
+    {
+        if !self.config.get_prefer_replica_reads() {
+            return self.get_key(key).await;
+        }
+
+        timed!("GET", self.pool.next().replicas().get(self.add_prefix(key)))
+            .change_command_context(errors::RedisError::GetFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -170,10 +528,20 @@ impl super::RedisConnectionPool {
     where
         V: Into<MultipleKeys> + Unpin + Send + 'static,
     {
-        self.pool
-            .exists(key)
-            .await
-            .change_context(errors::RedisError::GetFailed)
+        timed!("EXISTS", self.pool.exists(self.add_prefix(key)))
+            .change_command_context(errors::RedisError::GetFailed)
+    }
+
+    /// Returns the number of the provided keys that exist.
+    /// A key that is specified multiple times and exists is counted multiple times.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn keys_exist(&self, keys: Vec<String>) -> CustomResult<usize, errors::RedisError> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        timed!("EXISTS", self.pool.exists(self.add_prefix_to_keys(keys)))
+            .change_command_context(errors::RedisError::GetFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -194,12 +562,203 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::JsonDeserializationFailed)
     }
 
+    /// Store raw bytes against the key without any UTF-8 conversion.
+    /// The default TTL is used when `ttl` (in seconds) is not provided.
+    #[instrument(level = "DEBUG", skip(self, value))]
+    pub async fn set_key_bytes(
+        &self,
+        key: &str,
+        value: &[u8],
+        ttl: Option<i64>,
+    ) -> CustomResult<(), errors::RedisError> {
+        match ttl {
+            Some(seconds) => self.set_key_with_expiry(key, value, seconds).await,
+            None => self.set_key(key, value).await,
+        }
+    }
+
+    /// Fetch the raw bytes stored against the key, returning `None` if the key does not exist.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_key_bytes(
+        &self,
+        key: &str,
+    ) -> CustomResult<Option<Vec<u8>>, errors::RedisError> {
+        self.get_key::<Option<Vec<u8>>>(key).await
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn delete_key(&self, key: &str) -> CustomResult<DelReply, errors::RedisError> {
-        self.pool
-            .del(key)
-            .await
-            .change_context(errors::RedisError::DeleteFailed)
+        self.ensure_writable()?;
+        timed!("DEL", self.pool.del(self.add_prefix(key)))
+            .change_command_context(errors::RedisError::DeleteFailed)
+    }
+
+    /// Delete all the keys starting with `extra_prefix`, on top of the prefix of this pool, and
+    /// return the number of deleted keys. Keys are found using `SCAN` and each page of results is
+    /// removed with `UNLINK`. In cluster mode, every node is scanned and the keys of a page are
+    /// unlinked one slot at a time. Fails without deleting anything if the resulting prefix is
+    /// empty.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn flush_prefix(&self, extra_prefix: &str) -> CustomResult<u64, errors::RedisError> {
+        self.ensure_writable()?;
+        let prefix = self.add_prefix(extra_prefix);
+        fp_utils::when(prefix.is_empty(), || {
+            Err(report!(errors::RedisError::EmptyFlushPrefix))
+        })?;
+        let pattern = format!("{}*", escape_glob_pattern(&prefix));
+
+        let client = self.pool.next();
+        let is_clustered = client.is_clustered();
+        let mut pages = std::pin::pin!(if is_clustered {
+            futures::future::Either::Left(client.scan_cluster(
+                pattern,
+                Some(FLUSH_PREFIX_BATCH_SIZE),
+                None,
+            ))
+        } else {
+            futures::future::Either::Right(client.scan(
+                pattern,
+                Some(FLUSH_PREFIX_BATCH_SIZE),
+                None,
+            ))
+        });
+        let mut deleted_count = 0;
+        while let Some(page) = pages.next().await {
+            let keys = page
+                .change_context(errors::RedisError::DeleteFailed)?
+                .take_results()
+                .unwrap_or_default();
+            let batches = if is_clustered {
+                group_keys_by_slot(keys)
+            } else {
+                vec![keys]
+            };
+            for keys in batches.into_iter().filter(|keys| !keys.is_empty()) {
+                deleted_count += timed!("UNLINK", self.pool.unlink::<u64, _>(keys))
+                    .change_command_context(errors::RedisError::DeleteFailed)?;
+            }
+        }
+        Ok(deleted_count)
+    }
+
+    /// Get the value of the key, computing it with `compute` and storing it with the provided TTL
+    /// (in seconds) if the key does not exist.
+    #[instrument(level = "DEBUG", skip(self, compute))]
+    pub async fn get_or_set_with<F, Fut>(
+        &self,
+        key: &str,
+        ttl: i64,
+        compute: F,
+    ) -> CustomResult<String, errors::RedisError>
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: std::future::Future<Output = CustomResult<String, errors::RedisError>> + Send,
+    {
+        self.get_or_set_with_lock(key, ttl, None, compute).await
+    }
+
+    /// Same as [`Self::get_or_set_with`], but if `lock_ttl` is provided, only the caller acquiring a
+    /// short lived lock computes the value on a miss. Other callers poll the key every
+    /// [`LOCK_POLL_INTERVAL`] for up to `lock_ttl`, until the value is stored or the lock is
+    /// released, computing the value themselves only if it is still absent. The lock is released
+    /// whether or not computing or storing the value succeeds.
+    #[instrument(level = "DEBUG", skip(self, compute))]
+    pub async fn get_or_set_with_lock<F, Fut>(
+        &self,
+        key: &str,
+        ttl: i64,
+        lock_ttl: Option<Duration>,
+        compute: F,
+    ) -> CustomResult<String, errors::RedisError>
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: std::future::Future<Output = CustomResult<String, errors::RedisError>> + Send,
+    {
+        if let Some(value) = self.get_key::<Option<String>>(key).await? {
+            return Ok(value);
+        }
+
+        let lock_key = format!("{key}_lock");
+        let is_lock_acquired = match lock_ttl {
+            Some(lock_ttl) => {
+                let lock_ttl_in_secs = i64::try_from(lock_ttl.as_secs().max(1))
+                    .change_context(errors::RedisError::SetExFailed)?;
+                let is_lock_acquired = self
+                    .set_key_if_not_exists_with_expiry(&lock_key, "locked", Some(lock_ttl_in_secs))
+                    .await?
+                    == SetnxReply::KeySet;
+
+                if !is_lock_acquired {
+                    if let Some(value) =
+                        self.wait_for_locked_value(key, &lock_key, lock_ttl).await?
+                    {
+                        return Ok(value);
+                    }
+                }
+                is_lock_acquired
+            }
+            None => false,
+        };
+
+        let result = async {
+            let value = compute().await?;
+            self.set_key_with_expiry(key, value.as_str(), ttl).await?;
+            Ok::<_, error_stack::Report<errors::RedisError>>(value)
+        }
+        .await;
+
+        if is_lock_acquired {
+            self.delete_key(&lock_key)
+                .await
+                .map_err(|error| logger::error!(?error, "Failed to release lock"))
+                .ok();
+        }
+
+        result
+    }
+
+    /// Poll `key` until it is set, `lock_key` is released or `lock_ttl` elapses, returning the
+    /// value if it was set in the meantime.
+    async fn wait_for_locked_value(
+        &self,
+        key: &str,
+        lock_key: &str,
+        lock_ttl: Duration,
+    ) -> CustomResult<Option<String>, errors::RedisError> {
+        let deadline = tokio::time::Instant::now() + lock_ttl;
+        loop {
+            tokio::time::sleep(LOCK_POLL_INTERVAL.min(lock_ttl)).await;
+
+            // The lock holder stores the value before releasing the lock, so the value is read
+            // after checking the lock, to not miss a value stored in between
+            let is_lock_held = self.keys_exist(vec![lock_key.to_owned()]).await? > 0;
+            let value = self.get_key::<Option<String>>(key).await?;
+            if value.is_some() || !is_lock_held || tokio::time::Instant::now() >= deadline {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Rename `src` to `dst` only if `dst` does not exist, returning whether the key was renamed.
+    /// Fails with [`errors::RedisError::NotFound`] if `src` does not exist.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn rename_key_nx(
+        &self,
+        src: &str,
+        dst: &str,
+    ) -> CustomResult<bool, errors::RedisError> {
+        timed!(
+            "RENAMENX",
+            self.pool
+                .renamenx(self.add_prefix(src), self.add_prefix(dst))
+        )
+        .map_err(|err| {
+            if err.details().to_lowercase().contains("no such key") {
+                report!(err).change_context(errors::RedisError::NotFound)
+            } else {
+                command_error(err, errors::RedisError::RenameFailed)
+            }
+        })
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -213,10 +772,18 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisValue> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        self.pool
-            .set(key, value, Some(Expiration::EX(seconds)), None, false)
-            .await
-            .change_context(errors::RedisError::SetExFailed)
+        self.ensure_writable()?;
+        timed!(
+            "SET",
+            self.pool.set(
+                self.add_prefix(key),
+                value,
+                Some(Expiration::EX(seconds)),
+                None,
+                false,
+            )
+        )
+        .change_command_context(errors::RedisError::SetExFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -230,9 +797,11 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisValue> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        self.pool
-            .set(
-                key,
+        self.ensure_writable()?;
+        timed!(
+            "SET",
+            self.pool.set(
+                self.add_prefix(key),
                 value,
                 Some(Expiration::EX(
                     seconds.unwrap_or(self.config.default_ttl.into()),
@@ -240,8 +809,8 @@ impl super::RedisConnectionPool {
                 Some(SetOptions::NX),
                 false,
             )
-            .await
-            .change_context(errors::RedisError::SetFailed)
+        )
+        .change_command_context(errors::RedisError::SetFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -250,10 +819,8 @@ impl super::RedisConnectionPool {
         key: &str,
         seconds: i64,
     ) -> CustomResult<(), errors::RedisError> {
-        self.pool
-            .expire(key, seconds)
-            .await
-            .change_context(errors::RedisError::SetExpiryFailed)
+        timed!("EXPIRE", self.pool.expire(self.add_prefix(key), seconds))
+            .change_command_context(errors::RedisError::SetExpiryFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -262,10 +829,64 @@ impl super::RedisConnectionPool {
         key: &str,
         timestamp: i64,
     ) -> CustomResult<(), errors::RedisError> {
-        self.pool
-            .expire_at(key, timestamp)
-            .await
-            .change_context(errors::RedisError::SetExpiryFailed)
+        timed!(
+            "EXPIREAT",
+            self.pool.expire_at(self.add_prefix(key), timestamp)
+        )
+        .change_command_context(errors::RedisError::SetExpiryFailed)
+    }
+
+    /// Store a session value which expires at the given time.
+    /// Fails with [`errors::RedisError::InvalidTtl`] if the expiry is not in the future.
+    #[instrument(level = "DEBUG", skip(self, value))]
+    pub async fn set_session<V>(
+        &self,
+        key: &str,
+        value: V,
+        expires_at: PrimitiveDateTime,
+    ) -> CustomResult<(), errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+    {
+        let ttl = (expires_at - common_utils::date_time::now()).whole_seconds();
+        fp_utils::when(ttl <= 0, || {
+            Err(report!(errors::RedisError::InvalidTtl))
+                .attach_printable(format!("Session expiry {expires_at} is not in the future"))
+        })?;
+
+        self.set_key_with_expiry(key, value, ttl).await
+    }
+
+    /// Get the remaining time to live of a session, returning `None` if the key does not exist
+    /// or has no expiry set.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_session_ttl(
+        &self,
+        key: &str,
+    ) -> CustomResult<Option<Duration>, errors::RedisError> {
+        let ttl: i64 = timed!("TTL", self.pool.ttl(self.add_prefix(key)))
+            .change_command_context(errors::RedisError::GetFailed)?;
+
+        // TTL returns -2 if the key does not exist and -1 if the key has no expiry
+        Ok(u64::try_from(ttl).ok().map(Duration::from_secs))
+    }
+
+    /// Check whether the remaining time to live of `key` is below `threshold`. Returns `None` if
+    /// the key does not exist or has no expiry.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn is_ttl_below(
+        &self,
+        key: &str,
+        threshold: Duration,
+    ) -> CustomResult<Option<bool>, errors::RedisError> {
+        let ttl_in_millis: i64 = timed!("PTTL", self.pool.pttl(self.add_prefix(key)))
+            .change_command_context(errors::RedisError::GetFailed)?;
+
+        // PTTL returns -2 if the key does not exist and -1 if the key has no expiry
+        Ok(u64::try_from(ttl_in_millis)
+            .ok()
+            .map(|ttl_in_millis| Duration::from_millis(ttl_in_millis) < threshold))
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -279,11 +900,8 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisMap> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        let output: Result<(), _> = self
-            .pool
-            .hset(key, values)
-            .await
-            .change_context(errors::RedisError::SetHashFailed);
+        let output: Result<(), _> = timed!("HSET", self.pool.hset(self.add_prefix(key), values))
+            .change_command_context(errors::RedisError::SetHashFailed);
         // setting expiry for the key
         output
             .async_and_then(|_| {
@@ -292,6 +910,69 @@ impl super::RedisConnectionPool {
             .await
     }
 
+    /// Serialize the value and write each of its top level fields as a field of the hash in a
+    /// single `HSET`. Field values are stored JSON encoded. Fails with
+    /// [`errors::RedisError::JsonSerializationFailed`] if the value does not serialize to an object.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn serialize_and_set_hash_fields<T>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<i64>,
+    ) -> CustomResult<(), errors::RedisError>
+    where
+        T: serde::Serialize + Debug,
+    {
+        let serde_json::Value::Object(fields) = serde_json::to_value(value)
+            .change_context(errors::RedisError::JsonSerializationFailed)?
+        else {
+            return Err(report!(errors::RedisError::JsonSerializationFailed))
+                .attach_printable("Value does not serialize to a JSON object");
+        };
+
+        let fields = fields
+            .into_iter()
+            .map(|(field, value)| (field, value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        self.set_hash_fields(key, fields, ttl).await
+    }
+
+    /// Read all the fields of the hash and deserialize them into the given type, returning `None`
+    /// if the key does not exist. Field values that are not valid JSON are read as strings.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_hash_as<T>(&self, key: &str) -> CustomResult<Option<T>, errors::RedisError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let fields: RedisValue = timed!("HGETALL", self.pool.hgetall(self.add_prefix(key)))
+            .change_command_context(errors::RedisError::GetHashFieldFailed)?;
+        let fields = redis_map_to_string_map(fields)?;
+
+        if fields.is_empty() {
+            return Ok(None);
+        }
+
+        let fields = fields
+            .into_iter()
+            .map(|(field, value)| {
+                let value = serde_json::from_str(&value)
+                    .unwrap_or_else(|_| serde_json::Value::String(value));
+                (field, value)
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        serde_json::from_value(serde_json::Value::Object(fields))
+            .map(Some)
+            .change_context(errors::RedisError::JsonDeserializationFailed)
+            .attach_printable_lazy(|| {
+                format!(
+                    "Unable to deserialize hash into {}",
+                    std::any::type_name::<T>()
+                )
+            })
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_hash_field_if_not_exist<V>(
         &self,
@@ -304,11 +985,11 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisValue> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        let output: Result<HsetnxReply, _> = self
-            .pool
-            .hsetnx(key, field, value)
-            .await
-            .change_context(errors::RedisError::SetHashFieldFailed);
+        let output: Result<HsetnxReply, _> = timed!(
+            "HSETNX",
+            self.pool.hsetnx(self.add_prefix(key), field, value)
+        )
+        .change_command_context(errors::RedisError::SetHashFieldFailed);
 
         output
             .async_and_then(|inner| async {
@@ -368,7 +1049,7 @@ impl super::RedisConnectionPool {
         Ok(self
             .pool
             .next()
-            .hscan::<&str, &str>(key, pattern, count)
+            .hscan::<String, &str>(self.add_prefix(key), pattern, count)
             .filter_map(|value| async move {
                 match value {
                     Ok(mut v) => {
@@ -418,10 +1099,8 @@ impl super::RedisConnectionPool {
     where
         V: FromRedis + Unpin + Send + 'static,
     {
-        self.pool
-            .hget(key, field)
-            .await
-            .change_context(errors::RedisError::GetHashFieldFailed)
+        timed!("HGET", self.pool.hget(self.add_prefix(key), field))
+            .change_command_context(errors::RedisError::GetHashFieldFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -455,10 +1134,8 @@ impl super::RedisConnectionPool {
         V: TryInto<MultipleValues> + Debug + Send,
         V::Error: Into<fred::error::RedisError> + Send,
     {
-        self.pool
-            .sadd(key, members)
-            .await
-            .change_context(errors::RedisError::SetAddMembersFailed)
+        timed!("SADD", self.pool.sadd(self.add_prefix(key), members))
+            .change_command_context(errors::RedisError::SetAddMembersFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -472,8 +1149,62 @@ impl super::RedisConnectionPool {
         F: TryInto<MultipleOrderedPairs> + Debug + Send + Sync,
         F::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        self.pool
-            .xadd(stream, false, None, entry_id, fields)
+        self.ensure_writable()?;
+        timed!(
+            "XADD",
+            self.pool
+                .xadd(self.add_prefix(stream), false, None, entry_id, fields)
+        )
+        .change_command_context(errors::RedisError::StreamAppendFailed)
+    }
+
+    /// Append the entries to the stream in a single pipeline, returning the IDs assigned to them.
+    /// If `maxlen` is provided, the stream is approximately trimmed (`MAXLEN ~`) to that length.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_append_many(
+        &self,
+        stream: &str,
+        entries: Vec<HashMap<String, String>>,
+        maxlen: Option<u64>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        self.ensure_writable()?;
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let xcap = match maxlen {
+            Some(maxlen) => {
+                let maxlen =
+                    i64::try_from(maxlen).change_context(errors::RedisError::StreamAppendFailed)?;
+                Some(
+                    XCap::try_from(("MAXLEN", "~", maxlen))
+                        .change_context(errors::RedisError::StreamAppendFailed)?,
+                )
+            }
+            None => None,
+        };
+        let stream = self.add_prefix(stream);
+        let pipeline = self.pool.next().pipeline();
+
+        for fields in entries {
+            let id = &RedisEntryId::AutoGeneratedID;
+            match xcap.clone() {
+                Some(xcap) => {
+                    pipeline
+                        .xadd::<(), _, _, _, _>(stream.as_str(), false, xcap, id, fields)
+                        .await
+                }
+                None => {
+                    pipeline
+                        .xadd::<(), _, _, _, _>(stream.as_str(), false, None, id, fields)
+                        .await
+                }
+            }
+            .change_context(errors::RedisError::StreamAppendFailed)?;
+        }
+
+        pipeline
+            .all()
             .await
             .change_context(errors::RedisError::StreamAppendFailed)
     }
@@ -487,10 +1218,8 @@ impl super::RedisConnectionPool {
     where
         Ids: Into<MultipleStrings> + Debug + Send + Sync,
     {
-        self.pool
-            .xdel(stream, ids)
-            .await
-            .change_context(errors::RedisError::StreamDeleteFailed)
+        timed!("XDEL", self.pool.xdel(self.add_prefix(stream), ids))
+            .change_command_context(errors::RedisError::StreamDeleteFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -503,10 +1232,8 @@ impl super::RedisConnectionPool {
         C: TryInto<XCap> + Debug + Send + Sync,
         C::Error: Into<fred::error::RedisError> + Send + Sync,
     {
-        self.pool
-            .xtrim(stream, xcap)
-            .await
-            .change_context(errors::RedisError::StreamTrimFailed)
+        timed!("XTRIM", self.pool.xtrim(self.add_prefix(stream), xcap))
+            .change_command_context(errors::RedisError::StreamTrimFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -519,10 +1246,8 @@ impl super::RedisConnectionPool {
     where
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
-        self.pool
-            .xack(stream, group, ids)
-            .await
-            .change_context(errors::RedisError::StreamAcknowledgeFailed)
+        timed!("XACK", self.pool.xack(self.add_prefix(stream), group, ids))
+            .change_command_context(errors::RedisError::StreamAcknowledgeFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -530,10 +1255,12 @@ impl super::RedisConnectionPool {
     where
         K: Into<RedisKey> + Debug + Send + Sync,
     {
-        self.pool
-            .xlen(stream)
-            .await
-            .change_context(errors::RedisError::GetLengthFailed)
+        let stream: RedisKey = stream.into();
+        timed!(
+            "XLEN",
+            self.pool.xlen(self.add_prefix(&stream.as_str_lossy()))
+        )
+        .change_command_context(errors::RedisError::GetLengthFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -547,21 +1274,23 @@ impl super::RedisConnectionPool {
         K: Into<MultipleKeys> + Debug + Send + Sync,
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
-        self.pool
-            .xread_map(
+        timed!(
+            "XREAD",
+            self.pool.xread_map(
                 Some(read_count.unwrap_or(self.config.default_stream_read_count)),
                 None,
-                streams,
+                self.add_prefix_to_keys(streams),
                 ids,
             )
-            .await
-            .map_err(|err| match err.kind() {
-                RedisErrorKind::NotFound | RedisErrorKind::Parse => {
-                    report!(err).change_context(errors::RedisError::StreamEmptyOrNotAvailable)
-                }
-                _ => report!(err).change_context(errors::RedisError::StreamReadFailed),
-            })
-    }
+        )
+        .map(|response| self.strip_prefix_from_stream_response(response))
+        .map_err(|err| match err.kind() {
+            RedisErrorKind::NotFound | RedisErrorKind::Parse => {
+                report!(err).change_context(errors::RedisError::StreamEmptyOrNotAvailable)
+            }
+            _ => command_error(err, errors::RedisError::StreamReadFailed),
+        })
+    }
 
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn stream_read_with_options<K, Ids>(
@@ -576,22 +1305,342 @@ impl super::RedisConnectionPool {
         K: Into<MultipleKeys> + Debug + Send + Sync,
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
+        self.stream_read_with_timeout(streams, ids, count, block, group, None)
+            .await
+    }
+
+    /// Same as `stream_read_with_options`, with an optional command timeout overriding
+    /// `default_command_timeout`. Timeouts above `MAX_COMMAND_TIMEOUT` are clamped.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_read_with_timeout<K, Ids>(
+        &self,
+        streams: K,
+        ids: Ids,
+        count: Option<u64>,
+        block: Option<u64>,          // timeout in milliseconds
+        group: Option<(&str, &str)>, // (group_name, consumer_name)
+        timeout: Option<Duration>,
+    ) -> CustomResult<XReadResponse<String, String, String, Option<String>>, errors::RedisError>
+    where
+        K: Into<MultipleKeys> + Debug + Send + Sync,
+        Ids: Into<MultipleIDs> + Debug + Send + Sync,
+    {
+        let streams = self.add_prefix_to_keys(streams);
+        let client = self.pool.next().with_options(&Options {
+            timeout: timeout.map(clamp_command_timeout),
+            ..Default::default()
+        });
         match group {
-            Some((group_name, consumer_name)) => {
-                self.pool
-                    .xreadgroup_map(group_name, consumer_name, count, block, false, streams, ids)
-                    .await
-            }
-            None => self.pool.xread_map(count, block, streams, ids).await,
+            Some((group_name, consumer_name)) => timed!(
+                "XREADGROUP",
+                client.xreadgroup_map(group_name, consumer_name, count, block, false, streams, ids)
+            ),
+            None => timed!("XREAD", client.xread_map(count, block, streams, ids)),
         }
+        .map(|response| self.strip_prefix_from_stream_response(response))
         .map_err(|err| match err.kind() {
             RedisErrorKind::NotFound | RedisErrorKind::Parse => {
                 report!(err).change_context(errors::RedisError::StreamEmptyOrNotAvailable)
             }
-            _ => report!(err).change_context(errors::RedisError::StreamReadFailed),
+            RedisErrorKind::Timeout => {
+                report!(err).change_context(errors::RedisError::CommandTimeout)
+            }
+            _ => command_error(err, errors::RedisError::StreamReadFailed),
+        })
+    }
+
+    /// Summary of the entries of the stream read by the consumer group but not acknowledged yet
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_pending_summary(
+        &self,
+        stream: &str,
+        group: &str,
+    ) -> CustomResult<PendingSummary, errors::RedisError> {
+        timed!(
+            "XPENDING",
+            self.pool
+                .xpending::<PendingSummary, _, _, _>(self.add_prefix(stream), group, ())
+        )
+        .map_err(|err| match err.kind() {
+            RedisErrorKind::Parse => {
+                report!(err).change_context(errors::RedisError::StreamParseFailed)
+            }
+            _ => command_error(err, errors::RedisError::StreamReadFailed),
         })
     }
 
+    /// Atomically increment the counter stored at `key` and return its new value. When `wrap_at`
+    /// is provided, the counter is reset to `0` on reaching it, so that the returned values cycle
+    /// through `0..wrap_at`.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn next_sequence(
+        &self,
+        key: &str,
+        wrap_at: Option<u64>,
+    ) -> CustomResult<u64, errors::RedisError> {
+        let key = self.add_prefix(key);
+        match wrap_at {
+            None => timed!("INCR", self.pool.incr::<u64, _>(key))
+                .change_command_context(errors::RedisError::SequenceUpdateFailed),
+            Some(0) => Err(report!(errors::RedisError::SequenceUpdateFailed))
+                .attach_printable("Sequence wraparound bound must be greater than zero"),
+            Some(wrap_at) => timed!(
+                "EVAL",
+                self.pool
+                    .eval::<u64, _, _, _>(WRAPPING_INCREMENT_SCRIPT, key, wrap_at.to_string())
+            )
+            .change_command_context(errors::RedisError::SequenceUpdateFailed),
+        }
+    }
+
+    /// Push `value` to the head of the list `queue`, returning the length of the queue
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn queue_push<V>(
+        &self,
+        queue: &str,
+        value: V,
+    ) -> CustomResult<u64, errors::RedisError>
+    where
+        V: TryInto<MultipleValues> + Debug + Send,
+        V::Error: Into<fred::error::RedisError> + Send,
+    {
+        self.ensure_writable()?;
+        timed!("LPUSH", self.pool.lpush(self.add_prefix(queue), value))
+            .change_command_context(errors::RedisError::QueuePushFailed)
+    }
+
+    /// Pop a value from the tail of the list `queue`, waiting up to `timeout` for one to be
+    /// pushed. Returns `None` if the timeout elapses with the queue still empty.
+    ///
+    /// The pool clients are configured to reject commands while a blocking command is in
+    /// flight, so `BRPOP` is issued on a dedicated connection which is closed once the command
+    /// completes. The timeout is clamped to [`MAX_COMMAND_TIMEOUT`] and a zero timeout blocks
+    /// until a value is available.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn queue_pop_blocking(
+        &self,
+        queue: &str,
+        timeout: Duration,
+    ) -> CustomResult<Option<String>, errors::RedisError> {
+        self.ensure_writable()?;
+        let timeout = clamp_command_timeout(timeout);
+        let client = self.connect_dedicated_client().await?;
+
+        let popped = timed!(
+            "BRPOP",
+            client.brpop::<Option<(String, String)>, _>(
+                self.add_prefix(queue),
+                timeout.as_secs_f64()
+            )
+        )
+        .change_command_context(errors::RedisError::QueuePopFailed);
+
+        close_dedicated_client(client).await;
+
+        popped.map(|popped| popped.map(|(_, value)| value))
+    }
+
+    /// Open a connection which is not shared with the pool, for commands that rely on
+    /// connection state such as blocking commands or `WATCH`
+    async fn connect_dedicated_client(&self) -> CustomResult<RedisClient, errors::RedisError> {
+        let client = self.pool.next().clone_new();
+        client.connect();
+        client
+            .wait_for_connect()
+            .await
+            .change_context(errors::RedisError::RedisConnectionError)?;
+        Ok(client)
+    }
+
+    /// Records a hit in the sliding window log stored in the sorted set at `key`, provided fewer
+    /// than `limit` hits were recorded within the last `window`. Returns whether the hit was
+    /// recorded.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn consume_sliding_window_token(
+        &self,
+        key: &str,
+        limit: u64,
+        window: Duration,
+    ) -> CustomResult<bool, errors::RedisError> {
+        let key = self.add_prefix(key);
+        let now = time::OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000;
+        let window_in_millis = i128::try_from(window.as_millis())
+            .change_context(errors::RedisError::SlidingWindowUpdateFailed)?;
+        let window_in_secs = window.as_secs().max(1);
+        let member = common_utils::generate_id_with_default_len("hit");
+
+        let recorded = timed!(
+            "EVAL",
+            self.pool.eval::<u64, _, _, _>(
+                SLIDING_WINDOW_SCRIPT,
+                key,
+                vec![
+                    (now - window_in_millis).to_string(),
+                    now.to_string(),
+                    limit.to_string(),
+                    member,
+                    window_in_secs.to_string(),
+                ],
+            )
+        )
+        .change_command_context(errors::RedisError::SlidingWindowUpdateFailed)?;
+        Ok(recorded == 1)
+    }
+
+    /// Determine the state of the circuit breaker of `connector`. The breaker opens for `window`
+    /// once `failure_threshold` failures were recorded within the last `window`, and is half open
+    /// afterwards until a success or a failure is recorded. A half open breaker which sees no
+    /// traffic for another `window` closes again.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn circuit_breaker_check(
+        &self,
+        connector: &str,
+        failure_threshold: u32,
+        window: Duration,
+    ) -> CustomResult<CircuitState, errors::RedisError> {
+        let keys = CircuitBreakerKeys::new(connector);
+        if self.exists::<()>(&keys.open).await? {
+            return Ok(CircuitState::Open);
+        }
+
+        let failures_key = self.add_prefix(&keys.failures);
+        let now = time::OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000;
+        let window_in_millis = i128::try_from(window.as_millis())
+            .change_context(errors::RedisError::CircuitBreakerUpdateFailed)?;
+        #[allow(clippy::as_conversions)]
+        let window_start_score = (now - window_in_millis) as f64;
+        timed!(
+            "ZREMRANGEBYSCORE",
+            self.pool.zremrangebyscore::<u64, _, _, _>(
+                &failures_key,
+                f64::NEG_INFINITY,
+                window_start_score
+            )
+        )
+        .change_command_context(errors::RedisError::CircuitBreakerUpdateFailed)?;
+        let failures = timed!("ZCARD", self.pool.zcard::<u64, _>(&failures_key))
+            .change_command_context(errors::RedisError::CircuitBreakerUpdateFailed)?;
+
+        if failures >= u64::from(failure_threshold) {
+            self.open_circuit(&keys, window).await?;
+            self.delete_key(&keys.failures).await?;
+            Ok(CircuitState::Open)
+        } else if self.exists::<()>(&keys.tripped).await? {
+            Ok(CircuitState::HalfOpen)
+        } else {
+            Ok(CircuitState::Closed)
+        }
+    }
+
+    /// Record a failed request to `connector`, counted against the failure threshold for
+    /// `window`. A failure while the breaker is half open opens it again right away.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn record_failure(
+        &self,
+        connector: &str,
+        window: Duration,
+    ) -> CustomResult<(), errors::RedisError> {
+        let keys = CircuitBreakerKeys::new(connector);
+        if self.exists::<()>(&keys.tripped).await? {
+            self.open_circuit(&keys, window).await
+        } else {
+            self.consume_sliding_window_token(&keys.failures, u64::MAX, window)
+                .await
+                .change_context(errors::RedisError::CircuitBreakerUpdateFailed)
+                .map(|_| ())
+        }
+    }
+
+    /// Record a successful request to `connector`, closing its circuit breaker
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn record_success(&self, connector: &str) -> CustomResult<(), errors::RedisError> {
+        let keys = CircuitBreakerKeys::new(connector);
+        for key in [&keys.open, &keys.tripped, &keys.failures] {
+            self.delete_key(key).await?;
+        }
+        Ok(())
+    }
+
+    async fn open_circuit(
+        &self,
+        keys: &CircuitBreakerKeys,
+        window: Duration,
+    ) -> CustomResult<(), errors::RedisError> {
+        let window_in_secs = i64::try_from(window.as_secs().max(1))
+            .change_context(errors::RedisError::CircuitBreakerUpdateFailed)?;
+        self.set_key_with_expiry(&keys.open, "open", window_in_secs)
+            .await?;
+        // The breaker stays tripped for one more window after the open period, as half open
+        self.set_key_with_expiry(&keys.tripped, "tripped", window_in_secs.saturating_mul(2))
+            .await
+    }
+
+    /// Run the commands queued by `build` in a MULTI/EXEC transaction, after WATCHing the
+    /// provided keys. Returns `None` if the transaction was aborted because a watched key was
+    /// modified concurrently.
+    ///
+    /// The key prefix is applied to the watched keys, and by [`RedisTransaction`] to the keys of
+    /// the queued commands.
+    #[instrument(level = "DEBUG", skip(self, build))]
+    pub async fn transaction<F, Fut>(
+        &self,
+        watch_keys: Vec<String>,
+        build: F,
+    ) -> CustomResult<Option<Vec<RedisValue>>, errors::RedisError>
+    where
+        F: FnOnce(RedisTransaction) -> Fut + Send,
+        Fut:
+            std::future::Future<Output = CustomResult<RedisTransaction, errors::RedisError>> + Send,
+    {
+        // WATCH has to be sent on the same connection as the transaction, and the pool
+        // connections are shared with other tasks whose EXEC or UNWATCH would clear the watch
+        let client = self.connect_dedicated_client().await?;
+        let watch_keys = watch_keys
+            .iter()
+            .map(|key| self.add_prefix(key))
+            .collect::<Vec<_>>();
+
+        let result = self.watch_and_exec(&client, watch_keys, build).await;
+        close_dedicated_client(client).await;
+        result
+    }
+
+    async fn watch_and_exec<F, Fut>(
+        &self,
+        client: &RedisClient,
+        watch_keys: Vec<String>,
+        build: F,
+    ) -> CustomResult<Option<Vec<RedisValue>>, errors::RedisError>
+    where
+        F: FnOnce(RedisTransaction) -> Fut + Send,
+        Fut:
+            std::future::Future<Output = CustomResult<RedisTransaction, errors::RedisError>> + Send,
+    {
+        if !watch_keys.is_empty() {
+            client
+                .custom::<(), _>(
+                    CustomCommand::new_static("WATCH", ClusterHash::FirstKey, false),
+                    watch_keys,
+                )
+                .await
+                .change_context(errors::RedisError::TransactionFailed)?;
+        }
+
+        let transaction = RedisTransaction {
+            transaction: client.multi(),
+            prefix: self.get_composed_prefix(),
+            default_ttl: self.config.default_ttl,
+        };
+
+        // The dedicated connection is closed afterwards, so no UNWATCH is needed on failure
+        build(transaction)
+            .await?
+            .transaction
+            .exec(true)
+            .await
+            .change_context(errors::RedisError::TransactionFailed)
+    }
+
     //                                              Consumer Group API
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -609,10 +1658,39 @@ impl super::RedisConnectionPool {
             Err(errors::RedisError::InvalidRedisEntryId)?;
         }
 
-        self.pool
-            .xgroup_create(stream, group, id, true)
-            .await
-            .change_context(errors::RedisError::ConsumerGroupCreateFailed)
+        timed!(
+            "XGROUP CREATE",
+            self.pool
+                .xgroup_create(self.add_prefix(stream), group, id, true)
+        )
+        .change_command_context(errors::RedisError::ConsumerGroupCreateFailed)
+    }
+
+    /// Create the consumer group starting at `start_id`, along with the stream if it does not
+    /// exist. An already existing group (a `BUSYGROUP` error) is treated as success, so that this
+    /// can be called on every startup.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn ensure_consumer_group(
+        &self,
+        stream: &str,
+        group: &str,
+        start_id: &str,
+    ) -> CustomResult<(), errors::RedisError> {
+        match timed!(
+            "XGROUP CREATE",
+            self.pool
+                .xgroup_create::<(), _, _, _>(self.add_prefix(stream), group, start_id, true)
+        ) {
+            Ok(()) => Ok(()),
+            Err(err) if err.details().starts_with("BUSYGROUP") => {
+                logger::debug!(stream, group, "Consumer group already exists");
+                Ok(())
+            }
+            Err(err) => Err(command_error(
+                err,
+                errors::RedisError::ConsumerGroupCreateFailed,
+            )),
+        }
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -621,10 +1699,11 @@ impl super::RedisConnectionPool {
         stream: &str,
         group: &str,
     ) -> CustomResult<usize, errors::RedisError> {
-        self.pool
-            .xgroup_destroy(stream, group)
-            .await
-            .change_context(errors::RedisError::ConsumerGroupDestroyFailed)
+        timed!(
+            "XGROUP DESTROY",
+            self.pool.xgroup_destroy(self.add_prefix(stream), group)
+        )
+        .change_command_context(errors::RedisError::ConsumerGroupDestroyFailed)
     }
 
     // the number of pending messages that the consumer had before it was deleted
@@ -635,10 +1714,12 @@ impl super::RedisConnectionPool {
         group: &str,
         consumer: &str,
     ) -> CustomResult<usize, errors::RedisError> {
-        self.pool
-            .xgroup_delconsumer(stream, group, consumer)
-            .await
-            .change_context(errors::RedisError::ConsumerGroupRemoveConsumerFailed)
+        timed!(
+            "XGROUP DELCONSUMER",
+            self.pool
+                .xgroup_delconsumer(self.add_prefix(stream), group, consumer)
+        )
+        .change_command_context(errors::RedisError::ConsumerGroupRemoveConsumerFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -648,10 +1729,11 @@ impl super::RedisConnectionPool {
         group: &str,
         id: &RedisEntryId,
     ) -> CustomResult<String, errors::RedisError> {
-        self.pool
-            .xgroup_setid(stream, group, id)
-            .await
-            .change_context(errors::RedisError::ConsumerGroupSetIdFailed)
+        timed!(
+            "XGROUP SETID",
+            self.pool.xgroup_setid(self.add_prefix(stream), group, id)
+        )
+        .change_command_context(errors::RedisError::ConsumerGroupSetIdFailed)
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -667,9 +1749,10 @@ impl super::RedisConnectionPool {
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
         R: FromRedis + Unpin + Send + 'static,
     {
-        self.pool
-            .xclaim(
-                stream,
+        timed!(
+            "XCLAIM",
+            self.pool.xclaim(
+                self.add_prefix(stream),
                 group,
                 consumer,
                 min_idle_time,
@@ -680,8 +1763,58 @@ impl super::RedisConnectionPool {
                 false,
                 false,
             )
-            .await
-            .change_context(errors::RedisError::ConsumerGroupClaimFailed)
+        )
+        .change_command_context(errors::RedisError::ConsumerGroupClaimFailed)
+    }
+
+    /// Subscribe to the channel, with the key prefix of this pool applied to the channel name
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn subscribe_to_channel(
+        &self,
+        channel: &str,
+    ) -> CustomResult<(), errors::RedisError> {
+        timed!(
+            "SUBSCRIBE",
+            self.subscriber.subscribe(self.add_prefix(channel))
+        )
+        .change_command_context(errors::RedisError::SubscribeError)
+    }
+
+    /// Publish a message to the channel and return the number of subscribers that received it.
+    /// A count of zero indicates that nobody is listening on the channel.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn publish_with_count(
+        &self,
+        channel: &str,
+        message: &str,
+    ) -> CustomResult<u32, errors::RedisError> {
+        timed!(
+            "PUBLISH",
+            self.publisher.publish(self.add_prefix(channel), message)
+        )
+        .change_command_context(errors::RedisError::PublishError)
+    }
+
+    fn add_prefix_to_keys<K>(&self, keys: K) -> MultipleKeys
+    where
+        K: Into<MultipleKeys>,
+    {
+        keys.into()
+            .inner()
+            .into_iter()
+            .map(|key| self.add_prefix(&key.as_str_lossy()))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn strip_prefix_from_stream_response<V>(
+        &self,
+        response: HashMap<String, V>,
+    ) -> HashMap<String, V> {
+        response
+            .into_iter()
+            .map(|(stream, entries)| (self.strip_prefix(&stream).to_string(), entries))
+            .collect()
     }
 }
 
@@ -689,7 +1822,22 @@ impl super::RedisConnectionPool {
 mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used)]
 
-    use crate::{errors::RedisError, RedisConnectionPool, RedisEntryId, RedisSettings};
+    use std::{collections::HashMap, time::Duration};
+
+    use fred::{
+        interfaces::{HashesInterface, KeysInterface},
+        types::RedisKey,
+    };
+    use futures::StreamExt;
+
+    use super::{
+        clamp_command_timeout, command_error, group_keys_by_slot, redis_map_to_string_map,
+        MAX_COMMAND_TIMEOUT,
+    };
+    use crate::{
+        errors::RedisError, CircuitState, RedisConfig, RedisConnectionPool, RedisEntryId,
+        RedisSettings,
+    };
 
     #[tokio::test]
     async fn test_consumer_group_create() {
@@ -764,4 +1912,1482 @@ mod tests {
 
         assert!(is_success);
     }
+
+    #[tokio::test]
+    async fn test_set_and_get_key_bytes() {
+        let (fetched_value, missing_value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let value: &[u8] = &[0x00, 0x9f, 0x92, 0x96, 0xff, 0xfe, 0x0a];
+
+                // Act
+                pool.set_key_bytes("binary_key", value, Some(60))
+                    .await
+                    .unwrap();
+                let fetched_value = pool.get_key_bytes("binary_key").await.unwrap();
+                let missing_value = pool.get_key_bytes("binary key not exists").await.unwrap();
+
+                // Assert Setup
+                (fetched_value, missing_value)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(
+            fetched_value,
+            Some(vec![0x00, 0x9f, 0x92, 0x96, 0xff, 0xfe, 0x0a])
+        );
+        assert_eq!(missing_value, None);
+    }
+
+    #[tokio::test]
+    async fn test_publish_with_count() {
+        let (count_without_subscriber, count_with_subscriber) =
+            tokio::task::spawn_blocking(move || {
+                futures::executor::block_on(async {
+                    // Arrange
+                    let pool = RedisConnectionPool::new(&RedisSettings::default())
+                        .await
+                        .expect("failed to create redis connection pool");
+                    let channel = "test_publish_with_count_channel";
+
+                    // Act
+                    let count_without_subscriber =
+                        pool.publish_with_count(channel, "message").await.unwrap();
+                    pool.subscribe_to_channel(channel).await.unwrap();
+                    let count_with_subscriber =
+                        pool.publish_with_count(channel, "message").await.unwrap();
+
+                    // Assert Setup
+                    (count_without_subscriber, count_with_subscriber)
+                })
+            })
+            .await
+            .expect("Spawn block failure");
+
+        assert_eq!(count_without_subscriber, 0);
+        assert_eq!(count_with_subscriber, 1);
+    }
+
+    #[tokio::test]
+    async fn test_prefixed_channel_round_trip() {
+        let (count, message) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let pool = pool.with_config(
+                    "test_channel_prefix",
+                    crate::RedisConfig::from(&RedisSettings::default()),
+                );
+                let channel = "test_prefixed_channel_round_trip";
+                let messages = pool.message_stream();
+                futures::pin_mut!(messages);
+                pool.subscribe_to_channel(channel).await.unwrap();
+
+                // Act
+                let count = pool.publish_with_count(channel, "message").await.unwrap();
+
+                // Assert Setup
+                (count, messages.next().await)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            message,
+            Some((
+                "test_prefixed_channel_round_trip".to_string(),
+                "message".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_session_with_future_expiry() {
+        let ttl = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let expires_at = common_utils::date_time::now() + time::Duration::minutes(10);
+
+                // Act
+                pool.set_session("session_key", "session_value", expires_at)
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                pool.get_session_ttl("session_key").await.unwrap()
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        let ttl = ttl.expect("session should have a ttl");
+        assert!(ttl.as_secs() > 0 && ttl.as_secs() <= 600);
+    }
+
+    #[tokio::test]
+    async fn test_set_session_with_past_expiry() {
+        let is_invalid_ttl_error = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let expires_at = common_utils::date_time::now() - time::Duration::minutes(10);
+
+                // Act
+                let result = pool
+                    .set_session("expired_session_key", "session_value", expires_at)
+                    .await;
+
+                // Assert Setup
+                *result.unwrap_err().current_context() == RedisError::InvalidTtl
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(is_invalid_ttl_error);
+    }
+
+    #[tokio::test]
+    async fn test_serialize_and_set_hash_fields() {
+        #[derive(Debug, serde::Serialize)]
+        struct TestHash {
+            id: String,
+            amount: i64,
+            captured: bool,
+        }
+
+        let (fields, non_object_result) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let value = TestHash {
+                    id: "pay_1".to_string(),
+                    amount: 1000,
+                    captured: false,
+                };
+
+                // Act
+                pool.serialize_and_set_hash_fields("test_hash", &value, Some(60))
+                    .await
+                    .unwrap();
+                let fields: HashMap<String, String> = pool.pool.hgetall("test_hash").await.unwrap();
+                let non_object_result = pool
+                    .serialize_and_set_hash_fields("test_non_object_hash", &"value", None)
+                    .await;
+
+                // Assert Setup
+                (fields, non_object_result)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(
+            fields,
+            HashMap::from([
+                ("id".to_string(), "\"pay_1\"".to_string()),
+                ("amount".to_string(), "1000".to_string()),
+                ("captured".to_string(), "false".to_string()),
+            ])
+        );
+        assert_eq!(
+            *non_object_result.unwrap_err().current_context(),
+            RedisError::JsonSerializationFailed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_hash_as() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct TestHash {
+            id: String,
+            amount: i64,
+        }
+
+        #[derive(Debug, serde::Deserialize)]
+        struct MismatchedHash {
+            #[allow(dead_code)]
+            id: i64,
+        }
+
+        let (present, absent, is_deserialization_error) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let value = TestHash {
+                    id: "pay_1".to_string(),
+                    amount: 1000,
+                };
+                pool.serialize_and_set_hash_fields("test_get_hash", &value, Some(60))
+                    .await
+                    .unwrap();
+
+                // Act
+                let present = pool.get_hash_as::<TestHash>("test_get_hash").await.unwrap();
+                let absent = pool
+                    .get_hash_as::<TestHash>("test_get_hash_not_exists")
+                    .await
+                    .unwrap();
+                let mismatched = pool.get_hash_as::<MismatchedHash>("test_get_hash").await;
+
+                // Assert Setup
+                (
+                    present,
+                    absent,
+                    *mismatched.unwrap_err().current_context()
+                        == RedisError::JsonDeserializationFailed,
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(
+            present,
+            Some(TestHash {
+                id: "pay_1".to_string(),
+                amount: 1000,
+            })
+        );
+        assert_eq!(absent, None);
+        assert!(is_deserialization_error);
+    }
+
+    #[tokio::test]
+    async fn test_rename_key_nx() {
+        let (renamed, value, is_not_found_error) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let _ = pool.delete_key("rename_nx_dst").await;
+                pool.set_key("rename_nx_src", "value").await.unwrap();
+
+                // Act
+                let renamed = pool
+                    .rename_key_nx("rename_nx_src", "rename_nx_dst")
+                    .await
+                    .unwrap();
+                let value = pool.get_key::<String>("rename_nx_dst").await.unwrap();
+                let result = pool.rename_key_nx("rename_nx_src", "rename_nx_dst").await;
+
+                // Assert Setup
+                (
+                    renamed,
+                    value,
+                    *result.unwrap_err().current_context() == RedisError::NotFound,
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(renamed);
+        assert_eq!(value, "value");
+        assert!(is_not_found_error);
+    }
+
+    #[tokio::test]
+    async fn test_rename_key_nx_when_destination_exists() {
+        let (renamed, src_value, dst_value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.set_key("rename_nx_existing_src", "src").await.unwrap();
+                pool.set_key("rename_nx_existing_dst", "dst").await.unwrap();
+
+                // Act
+                let renamed = pool
+                    .rename_key_nx("rename_nx_existing_src", "rename_nx_existing_dst")
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                (
+                    renamed,
+                    pool.get_key::<String>("rename_nx_existing_src")
+                        .await
+                        .unwrap(),
+                    pool.get_key::<String>("rename_nx_existing_dst")
+                        .await
+                        .unwrap(),
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(!renamed);
+        assert_eq!(src_value, "src");
+        assert_eq!(dst_value, "dst");
+    }
+
+    #[tokio::test]
+    async fn test_stream_read_with_short_timeout() {
+        let is_command_timeout_error = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+
+                // Act
+                let result = pool
+                    .stream_read_with_timeout(
+                        "stream_read_timeout_test",
+                        "$",
+                        None,
+                        Some(5000),
+                        None,
+                        Some(Duration::from_millis(100)),
+                    )
+                    .await;
+
+                // Assert Setup
+                *result.unwrap_err().current_context() == RedisError::CommandTimeout
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(is_command_timeout_error);
+    }
+
+    #[test]
+    fn test_clamp_command_timeout() {
+        assert_eq!(
+            clamp_command_timeout(Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            clamp_command_timeout(MAX_COMMAND_TIMEOUT + Duration::from_secs(1)),
+            MAX_COMMAND_TIMEOUT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_key_with_environment_and_key_prefix() {
+        let (prefixed_value, stripped_key) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings {
+                    environment: Some("staging".to_string()),
+                    ..RedisSettings::default()
+                })
+                .await
+                .expect("failed to create redis connection pool");
+                let pool = pool.with_config(
+                    "subsystem",
+                    crate::RedisConfig::from(&RedisSettings::default()),
+                );
+
+                // Act
+                pool.set_key("environment_prefix_test", "value")
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                let prefixed_value: Option<String> = pool
+                    .pool
+                    .get("staging:subsystem:environment_prefix_test")
+                    .await
+                    .unwrap();
+                (
+                    prefixed_value,
+                    pool.strip_prefix("staging:subsystem:environment_prefix_test")
+                        .to_string(),
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(prefixed_value, Some("value".to_string()));
+        assert_eq!(stripped_key, "environment_prefix_test");
+    }
+
+    #[tokio::test]
+    async fn test_keys_exist() {
+        let count = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.set_key("keys_exist_present_1", "value").await.unwrap();
+                pool.set_key("keys_exist_present_2", "value").await.unwrap();
+                pool.delete_key("keys_exist_absent").await.unwrap();
+
+                // Act
+                pool.keys_exist(vec![
+                    "keys_exist_present_1".to_string(),
+                    "keys_exist_present_2".to_string(),
+                    "keys_exist_absent".to_string(),
+                    "keys_exist_present_1".to_string(),
+                ])
+                .await
+                .unwrap()
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        // The duplicated key is counted twice
+        assert_eq!(count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_consume_sliding_window_token() {
+        let consumed = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("sliding_window_test").await.unwrap();
+
+                // Act
+                let mut consumed = Vec::new();
+                for _ in 0..3 {
+                    consumed.push(
+                        pool.consume_sliding_window_token(
+                            "sliding_window_test",
+                            2,
+                            Duration::from_secs(60),
+                        )
+                        .await
+                        .unwrap(),
+                    );
+                }
+                consumed
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(consumed, vec![true, true, false]);
+    }
+
+    #[tokio::test]
+    async fn test_consume_sliding_window_token_concurrently() {
+        let consumed = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("sliding_window_concurrent_test")
+                    .await
+                    .unwrap();
+
+                // Act
+                futures::future::join_all((0..10).map(|_| {
+                    pool.consume_sliding_window_token(
+                        "sliding_window_concurrent_test",
+                        4,
+                        Duration::from_secs(60),
+                    )
+                }))
+                .await
+                .into_iter()
+                .map(Result::unwrap)
+                .filter(|consumed| *consumed)
+                .count()
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        // Concurrent callers must neither exceed the limit nor reject each other below it
+        assert_eq!(consumed, 4);
+    }
+
+    #[tokio::test]
+    async fn test_stream_append_many() {
+        let (ids, length) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("stream_append_many_test").await.unwrap();
+                let entries = (0..3)
+                    .map(|index| HashMap::from([("index".to_string(), index.to_string())]))
+                    .collect();
+
+                // Act
+                let ids = pool
+                    .stream_append_many("stream_append_many_test", entries, Some(100))
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                (
+                    ids,
+                    pool.stream_get_length("stream_append_many_test")
+                        .await
+                        .unwrap(),
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(ids.len(), 3);
+        assert_eq!(length, 3);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_is_aborted_on_conflicting_write() {
+        let (result, value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.set_key("transaction_balance", "10").await.unwrap();
+                let publisher = pool.publisher.clone();
+
+                // Act
+                let result = pool
+                    .transaction(
+                        vec!["transaction_balance".to_string()],
+                        move |transaction| {
+                            async move {
+                                // A write from another connection after WATCH aborts the transaction
+                                publisher
+                                    .set::<(), _, _>("transaction_balance", "20", None, None, false)
+                                    .await
+                                    .map_err(|error| {
+                                        error_stack::report!(error)
+                                            .change_context(RedisError::SetFailed)
+                                    })?;
+                                transaction.set_key("transaction_balance", "5").await?;
+                                Ok(transaction)
+                            }
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                (
+                    result,
+                    pool.get_key::<String>("transaction_balance").await.unwrap(),
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(result.is_none());
+        assert_eq!(value, "20");
+    }
+
+    #[tokio::test]
+    async fn test_transaction_watch_survives_concurrent_transaction_on_pool() {
+        let (result, nested_result, value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings {
+                    pool_size: 1,
+                    ..RedisSettings::default()
+                })
+                .await
+                .expect("failed to create redis connection pool");
+                pool.set_key("transaction_watched", "10").await.unwrap();
+                let publisher = pool.publisher.clone();
+                let mut nested_result = None;
+
+                // Act
+                let result = pool
+                    .transaction(vec!["transaction_watched".to_string()], |transaction| {
+                        let pool = &pool;
+                        let nested_result = &mut nested_result;
+                        async move {
+                            // The EXEC of another transaction on the single pool connection must
+                            // not clear the WATCH of this one
+                            *nested_result = pool
+                                .transaction(
+                                    vec!["transaction_unrelated".to_string()],
+                                    |nested| async move {
+                                        nested.set_key("transaction_unrelated", "1").await?;
+                                        Ok(nested)
+                                    },
+                                )
+                                .await?;
+                            publisher
+                                .set::<(), _, _>("transaction_watched", "20", None, None, false)
+                                .await
+                                .map_err(|error| {
+                                    error_stack::report!(error)
+                                        .change_context(RedisError::SetFailed)
+                                })?;
+                            transaction.set_key("transaction_watched", "5").await?;
+                            Ok(transaction)
+                        }
+                    })
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                let value = pool.get_key::<String>("transaction_watched").await.unwrap();
+                pool.delete_key("transaction_watched").await.unwrap();
+                pool.delete_key("transaction_unrelated").await.unwrap();
+                (result, nested_result, value)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(result.is_none());
+        assert!(nested_result.is_some());
+        assert_eq!(value, "20");
+    }
+
+    #[tokio::test]
+    async fn test_transaction_without_conflict() {
+        let (result, value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.set_key("transaction_no_conflict", "10").await.unwrap();
+
+                // Act
+                let result = pool
+                    .transaction(
+                        vec!["transaction_no_conflict".to_string()],
+                        |transaction| async move {
+                            transaction.set_key("transaction_no_conflict", "5").await?;
+                            Ok(transaction)
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                (
+                    result,
+                    pool.get_key::<String>("transaction_no_conflict")
+                        .await
+                        .unwrap(),
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(result.map(|values| values.len()), Some(1));
+        assert_eq!(value, "5");
+    }
+
+    #[tokio::test]
+    async fn test_transaction_applies_key_prefix_to_queued_commands() {
+        let (prefixed_value, unprefixed_count) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let tenant_pool =
+                    pool.with_config("tenant", RedisConfig::from(&RedisSettings::default()));
+                pool.delete_key("transaction_prefixed").await.unwrap();
+
+                // Act
+                tenant_pool
+                    .transaction(
+                        vec!["transaction_prefixed".to_string()],
+                        |transaction| async move {
+                            transaction.set_key("transaction_prefixed", "5").await?;
+                            Ok(transaction)
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                let prefixed_value = pool
+                    .get_key::<String>("tenant:transaction_prefixed")
+                    .await
+                    .unwrap();
+                let unprefixed_count = pool
+                    .keys_exist(vec!["transaction_prefixed".to_string()])
+                    .await
+                    .unwrap();
+                tenant_pool
+                    .delete_key("transaction_prefixed")
+                    .await
+                    .unwrap();
+                (prefixed_value, unprefixed_count)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(prefixed_value, "5");
+        assert_eq!(unprefixed_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_set_with_on_hit() {
+        let (value, is_computed) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.set_key("get_or_set_hit", "cached").await.unwrap();
+                let mut is_computed = false;
+
+                // Act
+                let value = pool
+                    .get_or_set_with("get_or_set_hit", 60, || {
+                        is_computed = true;
+                        async { Ok("computed".to_string()) }
+                    })
+                    .await
+                    .unwrap();
+
+                (value, is_computed)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(value, "cached");
+        assert!(!is_computed);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_set_with_on_miss() {
+        let (value, stored_value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("get_or_set_miss").await.unwrap();
+
+                // Act
+                let value = pool
+                    .get_or_set_with_lock(
+                        "get_or_set_miss",
+                        60,
+                        Some(Duration::from_secs(1)),
+                        || async { Ok("computed".to_string()) },
+                    )
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                (
+                    value,
+                    pool.get_key::<String>("get_or_set_miss").await.unwrap(),
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(value, "computed");
+        assert_eq!(stored_value, "computed");
+    }
+
+    #[tokio::test]
+    async fn test_get_or_set_with_lock_waits_for_lock_holder() {
+        let (value, elapsed) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("get_or_set_locked").await.unwrap();
+                pool.set_key_if_not_exists_with_expiry("get_or_set_locked_lock", "locked", Some(5))
+                    .await
+                    .unwrap();
+                let started_at = std::time::Instant::now();
+
+                // Act
+                let (value, ()) = futures::join!(
+                    pool.get_or_set_with_lock(
+                        "get_or_set_locked",
+                        60,
+                        Some(Duration::from_secs(5)),
+                        || async { Ok("computed_by_waiter".to_string()) },
+                    ),
+                    async {
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        pool.set_key_with_expiry("get_or_set_locked", "computed_by_holder", 60)
+                            .await
+                            .unwrap();
+                        pool.delete_key("get_or_set_locked_lock").await.unwrap();
+                    }
+                );
+
+                // Assert Setup
+                (value.unwrap(), started_at.elapsed())
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(value, "computed_by_holder");
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_get_or_set_with_lock_releases_lock_on_compute_failure() {
+        let (is_err, lock_count) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("get_or_set_failed").await.unwrap();
+                pool.delete_key("get_or_set_failed_lock").await.unwrap();
+
+                // Act
+                let result = pool
+                    .get_or_set_with_lock(
+                        "get_or_set_failed",
+                        60,
+                        Some(Duration::from_secs(5)),
+                        || async { Err(error_stack::report!(RedisError::UnknownResult)) },
+                    )
+                    .await;
+
+                // Assert Setup
+                (
+                    result.is_err(),
+                    pool.keys_exist(vec!["get_or_set_failed_lock".to_string()])
+                        .await
+                        .unwrap(),
+                )
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(is_err);
+        assert_eq!(lock_count, 0);
+    }
+
+    #[cfg(feature = "command_latency_metrics")]
+    #[tokio::test]
+    async fn test_get_key_records_one_latency_observation() {
+        let observations = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.set_key("latency_metrics_key", "value").await.unwrap();
+                super::command_latency::OBSERVATIONS
+                    .with(|observations| observations.borrow_mut().clear());
+
+                // Act
+                let _ = pool.get_key::<String>("latency_metrics_key").await;
+
+                // Assert setup
+                super::command_latency::OBSERVATIONS
+                    .with(|observations| observations.borrow().clone())
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(observations, vec!["GET"]);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_consumer_group_is_idempotent() {
+        let (first, second) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("ensure_consumer_group_stream")
+                    .await
+                    .unwrap();
+
+                // Act
+                let first = pool
+                    .ensure_consumer_group("ensure_consumer_group_stream", "test_group", "$")
+                    .await;
+                let second = pool
+                    .ensure_consumer_group("ensure_consumer_group_stream", "test_group", "$")
+                    .await;
+
+                // Assert setup
+                (first, second)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stream_pending_summary_with_unacknowledged_entries() {
+        let summary = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let stream = "stream_pending_summary_test";
+                pool.delete_key(stream).await.unwrap();
+                pool.ensure_consumer_group(stream, "test_group", "$")
+                    .await
+                    .unwrap();
+                for index in 0..2 {
+                    pool.stream_append_entry(
+                        stream,
+                        &RedisEntryId::AutoGeneratedID,
+                        vec![("index", index.to_string())],
+                    )
+                    .await
+                    .unwrap();
+                }
+                pool.stream_read_with_options(
+                    stream,
+                    ">",
+                    None,
+                    None,
+                    Some(("test_group", "test_consumer")),
+                )
+                .await
+                .unwrap();
+
+                // Act
+                pool.stream_pending_summary(stream, "test_group")
+                    .await
+                    .unwrap()
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(summary.total, 2);
+        assert!(summary.min_id.is_some());
+        assert!(summary.max_id.is_some());
+        assert_eq!(
+            summary.consumers,
+            HashMap::from([("test_consumer".to_string(), 2)])
+        );
+    }
+
+    #[test]
+    fn test_redis_map_to_string_map_with_map_frame() {
+        let value = fred::types::RedisValue::Map(
+            fred::types::RedisMap::try_from(vec![("name", "hyperswitch"), ("count", "2")]).unwrap(),
+        );
+
+        let map = redis_map_to_string_map(value).unwrap();
+
+        assert_eq!(
+            map,
+            HashMap::from([
+                ("name".to_string(), "hyperswitch".to_string()),
+                ("count".to_string(), "2".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_redis_map_to_string_map_with_array_of_pairs() {
+        let value = fred::types::RedisValue::Array(vec![
+            fred::types::RedisValue::Array(vec![
+                "consumer_1".into(),
+                fred::types::RedisValue::Integer(2),
+            ]),
+            fred::types::RedisValue::Array(vec![
+                "consumer_2".into(),
+                fred::types::RedisValue::Integer(1),
+            ]),
+        ]);
+
+        let map = redis_map_to_string_map(value).unwrap();
+
+        assert_eq!(
+            map,
+            HashMap::from([
+                ("consumer_1".to_string(), "2".to_string()),
+                ("consumer_2".to_string(), "1".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_redis_map_to_string_map_with_unexpected_shape() {
+        let odd_array = fred::types::RedisValue::Array(vec!["field".into()]);
+        let nested_value = fred::types::RedisValue::Array(vec![
+            "field".into(),
+            fred::types::RedisValue::Array(vec!["value".into()]),
+        ]);
+
+        for value in [odd_array, nested_value] {
+            assert_eq!(
+                redis_map_to_string_map(value)
+                    .unwrap_err()
+                    .current_context(),
+                &RedisError::StreamParseFailed
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_prefix_only_deletes_keys_under_prefix() {
+        let (deleted_count, flushed_exists, retained_exists, empty_prefix_error) =
+            tokio::task::spawn_blocking(move || {
+                futures::executor::block_on(async {
+                    // Arrange
+                    let pool = RedisConnectionPool::new(&RedisSettings::default())
+                        .await
+                        .expect("failed to create redis connection pool");
+                    let pool = pool.with_config(
+                        "flush_prefix_test",
+                        crate::RedisConfig::from(&RedisSettings::default()),
+                    );
+                    // The hash tags place the flushed keys on different slots
+                    for key in [
+                        "merchant_1:{slot_a}key_1",
+                        "merchant_1:{slot_b}key_2",
+                        "merchant_2:key_1",
+                    ] {
+                        pool.set_key(key, "value").await.unwrap();
+                    }
+
+                    // Act
+                    let deleted_count = pool.flush_prefix("merchant_1:").await.unwrap();
+
+                    // Assert Setup
+                    let mut flushed_exists = 0;
+                    for key in ["merchant_1:{slot_a}key_1", "merchant_1:{slot_b}key_2"] {
+                        flushed_exists += pool.keys_exist(vec![key.to_string()]).await.unwrap();
+                    }
+                    let retained_exists = pool
+                        .keys_exist(vec!["merchant_2:key_1".to_string()])
+                        .await
+                        .unwrap();
+                    pool.delete_key("merchant_2:key_1").await.unwrap();
+
+                    let unprefixed_pool = RedisConnectionPool::new(&RedisSettings::default())
+                        .await
+                        .expect("failed to create redis connection pool");
+                    let empty_prefix_error = unprefixed_pool.flush_prefix("").await.unwrap_err();
+
+                    (
+                        deleted_count,
+                        flushed_exists,
+                        retained_exists,
+                        empty_prefix_error,
+                    )
+                })
+            })
+            .await
+            .expect("Spawn block failure");
+
+        assert_eq!(deleted_count, 2);
+        assert_eq!(flushed_exists, 0);
+        assert_eq!(retained_exists, 1);
+        assert_eq!(
+            empty_prefix_error.current_context(),
+            &RedisError::EmptyFlushPrefix
+        );
+    }
+
+    #[test]
+    fn test_group_keys_by_slot() {
+        let first_slot_keys = [
+            RedisKey::from("flush_prefix_test:merchant_1:{slot_a}key_1"),
+            RedisKey::from("flush_prefix_test:merchant_1:{slot_a}key_2"),
+        ];
+        let second_slot_key = RedisKey::from("flush_prefix_test:merchant_1:{slot_b}key_1");
+        assert_ne!(
+            first_slot_keys[0].cluster_hash(),
+            second_slot_key.cluster_hash()
+        );
+
+        let mut batches = group_keys_by_slot(
+            first_slot_keys
+                .iter()
+                .cloned()
+                .chain([second_slot_key.clone()])
+                .collect(),
+        );
+        batches.sort_by_key(Vec::len);
+
+        assert_eq!(
+            batches,
+            vec![vec![second_slot_key], first_slot_keys.to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_cluster_redirect_is_reported_as_unexpected() {
+        let moved_error = fred::error::RedisError::new(
+            fred::error::RedisErrorKind::Unknown,
+            "MOVED 3999 127.0.0.1:6381",
+        );
+        let ask_error = fred::error::RedisError::new(
+            fred::error::RedisErrorKind::Unknown,
+            "ASK 3999 127.0.0.1:6381",
+        );
+        let other_error =
+            fred::error::RedisError::new(fred::error::RedisErrorKind::Unknown, "ERR unknown");
+
+        assert_eq!(
+            command_error(moved_error, RedisError::GetFailed).current_context(),
+            &RedisError::ClusterRedirectUnexpected
+        );
+        assert_eq!(
+            command_error(ask_error, RedisError::GetFailed).current_context(),
+            &RedisError::ClusterRedirectUnexpected
+        );
+        assert_eq!(
+            command_error(other_error, RedisError::GetFailed).current_context(),
+            &RedisError::GetFailed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_next_sequence() {
+        let sequence = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("next_sequence_test").await.unwrap();
+
+                // Act
+                let mut sequence = Vec::new();
+                for _ in 0..3 {
+                    sequence.push(
+                        pool.next_sequence("next_sequence_test", None)
+                            .await
+                            .unwrap(),
+                    );
+                }
+
+                // Assert Setup
+                pool.delete_key("next_sequence_test").await.unwrap();
+                sequence
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(sequence, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_next_sequence_with_wraparound() {
+        let (sequence, zero_bound_error) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("next_sequence_wrap_test").await.unwrap();
+
+                // Act
+                let mut sequence = Vec::new();
+                for _ in 0..5 {
+                    sequence.push(
+                        pool.next_sequence("next_sequence_wrap_test", Some(3))
+                            .await
+                            .unwrap(),
+                    );
+                }
+
+                // Assert Setup
+                let zero_bound_error = pool
+                    .next_sequence("next_sequence_wrap_test", Some(0))
+                    .await
+                    .unwrap_err();
+                pool.delete_key("next_sequence_wrap_test").await.unwrap();
+                (sequence, zero_bound_error)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(sequence, vec![1, 2, 0, 1, 2]);
+        assert_eq!(
+            zero_bound_error.current_context(),
+            &RedisError::SequenceUpdateFailed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_only_mode_rejects_writes() {
+        let (set_error, value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.set_key("read_only_mode_test", "value").await.unwrap();
+                pool.set_read_only_mode(true);
+
+                // Act
+                let set_error = pool
+                    .set_key("read_only_mode_test", "updated_value")
+                    .await
+                    .unwrap_err();
+                let value = pool.get_key::<String>("read_only_mode_test").await.unwrap();
+
+                // Assert Setup
+                pool.set_read_only_mode(false);
+                pool.delete_key("read_only_mode_test").await.unwrap();
+                (set_error, value)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(set_error.current_context(), &RedisError::ReadOnlyMode);
+        assert_eq!(value, "value");
+    }
+
+    #[tokio::test]
+    async fn test_read_only_mode_rejects_serialized_writes_with_expiry() {
+        let (set_error, value) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.serialize_and_set_key_with_expiry(
+                    "read_only_mode_serialized_test",
+                    "value",
+                    60,
+                )
+                .await
+                .unwrap();
+                pool.set_read_only_mode(true);
+
+                // Act
+                let set_error = pool
+                    .serialize_and_set_key_with_expiry(
+                        "read_only_mode_serialized_test",
+                        "updated_value",
+                        60,
+                    )
+                    .await
+                    .unwrap_err();
+                let value = pool
+                    .get_and_deserialize_key::<String>("read_only_mode_serialized_test", "String")
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                pool.set_read_only_mode(false);
+                pool.delete_key("read_only_mode_serialized_test")
+                    .await
+                    .unwrap();
+                (set_error, value)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(set_error.current_context(), &RedisError::ReadOnlyMode);
+        assert_eq!(value, "value");
+    }
+
+    #[tokio::test]
+    async fn test_queue_push_then_pop() {
+        let (queue_length, popped) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("queue_push_pop_test").await.unwrap();
+
+                // Act
+                pool.queue_push("queue_push_pop_test", "first")
+                    .await
+                    .unwrap();
+                let queue_length = pool
+                    .queue_push("queue_push_pop_test", "second")
+                    .await
+                    .unwrap();
+                let mut popped = Vec::new();
+                for _ in 0..2 {
+                    popped.push(
+                        pool.queue_pop_blocking("queue_push_pop_test", Duration::from_secs(1))
+                            .await
+                            .unwrap(),
+                    );
+                }
+
+                // Assert Setup
+                pool.delete_key("queue_push_pop_test").await.unwrap();
+                (queue_length, popped)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(queue_length, 2);
+        assert_eq!(
+            popped,
+            vec![Some("first".to_string()), Some("second".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queue_pop_blocking_timeout() {
+        let popped = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("queue_pop_timeout_test").await.unwrap();
+
+                // Act
+                pool.queue_pop_blocking("queue_pop_timeout_test", Duration::from_secs(1))
+                    .await
+                    .unwrap()
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(popped, None);
+    }
+
+    #[tokio::test]
+    async fn test_is_ttl_below() {
+        let (below, above, without_ttl, missing) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                pool.delete_key("ttl_below_without_ttl_test").await.unwrap();
+                pool.delete_key("ttl_below_missing_test").await.unwrap();
+                pool.set_key_with_expiry("ttl_below_test", "value", 100)
+                    .await
+                    .unwrap();
+                pool.set_key_without_modifying_ttl("ttl_below_without_ttl_test", "value")
+                    .await
+                    .unwrap();
+
+                // Act
+                let below = pool
+                    .is_ttl_below("ttl_below_test", Duration::from_secs(200))
+                    .await
+                    .unwrap();
+                let above = pool
+                    .is_ttl_below("ttl_below_test", Duration::from_secs(10))
+                    .await
+                    .unwrap();
+                let without_ttl = pool
+                    .is_ttl_below("ttl_below_without_ttl_test", Duration::from_secs(10))
+                    .await
+                    .unwrap();
+                let missing = pool
+                    .is_ttl_below("ttl_below_missing_test", Duration::from_secs(10))
+                    .await
+                    .unwrap();
+
+                // Assert Setup
+                pool.delete_key("ttl_below_test").await.unwrap();
+                pool.delete_key("ttl_below_without_ttl_test").await.unwrap();
+                (below, above, without_ttl, missing)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(below, Some(true));
+        assert_eq!(above, Some(false));
+        assert_eq!(without_ttl, None);
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_opens_after_threshold_failures() {
+        let states = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let window = Duration::from_secs(60);
+                pool.record_success("circuit_breaker_test").await.unwrap();
+
+                // Act
+                let mut states = vec![pool
+                    .circuit_breaker_check("circuit_breaker_test", 3, window)
+                    .await
+                    .unwrap()];
+                for _ in 0..3 {
+                    pool.record_failure("circuit_breaker_test", window)
+                        .await
+                        .unwrap();
+                    states.push(
+                        pool.circuit_breaker_check("circuit_breaker_test", 3, window)
+                            .await
+                            .unwrap(),
+                    );
+                }
+                pool.record_success("circuit_breaker_test").await.unwrap();
+                states.push(
+                    pool.circuit_breaker_check("circuit_breaker_test", 3, window)
+                        .await
+                        .unwrap(),
+                );
+
+                // Assert Setup
+                pool.record_success("circuit_breaker_test").await.unwrap();
+                states
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(
+            states,
+            vec![
+                CircuitState::Closed,
+                CircuitState::Closed,
+                CircuitState::Closed,
+                CircuitState::Open,
+                CircuitState::Closed,
+            ]
+        );
+    }
 }
@@ -18,10 +18,14 @@ pub enum RedisError {
     GetFailed,
     #[error("Failed to delete key value in Redis")]
     DeleteFailed,
+    #[error("Refusing to flush keys without a prefix")]
+    EmptyFlushPrefix,
     #[error("Failed to append entry to Redis stream")]
     StreamAppendFailed,
     #[error("Failed to read entries from Redis stream")]
     StreamReadFailed,
+    #[error("Failed to parse the reply of a Redis stream command")]
+    StreamParseFailed,
     #[error("Failed to get stream length")]
     GetLengthFailed,
     #[error("Failed to delete entries from Redis stream")]
@@ -68,4 +72,28 @@ pub enum RedisError {
     OnMessageError,
     #[error("Got an unknown result from redis")]
     UnknownResult,
+    #[error("Invalid TTL provided")]
+    InvalidTtl,
+    #[error("Failed to rename key in Redis")]
+    RenameFailed,
+    #[error("Redis command timed out")]
+    CommandTimeout,
+    #[error("Failed to update sliding window in Redis")]
+    SlidingWindowUpdateFailed,
+    #[error("Failed to execute transaction in Redis")]
+    TransactionFailed,
+    #[error("Failed to update sequence in Redis")]
+    SequenceUpdateFailed,
+    #[error("Redis is in read only mode, write commands are rejected")]
+    ReadOnlyMode,
+    #[error("Failed to push value to queue in Redis")]
+    QueuePushFailed,
+    #[error("Failed to pop value from queue in Redis")]
+    QueuePopFailed,
+    #[error("Failed to update circuit breaker state in Redis")]
+    CircuitBreakerUpdateFailed,
+    #[error(
+        "Redis replied with a cluster redirection, enable cluster mode if connecting to a cluster"
+    )]
+    ClusterRedirectUnexpected,
 }
@@ -3,8 +3,11 @@
 //! from `fred`'s internal data-types to custom data-types
 //!
 
+use std::collections::HashMap;
+
 use common_utils::errors::CustomResult;
 use fred::types::RedisValue as FredRedisValue;
+use masking::Secret;
 
 use crate::errors;
 
@@ -34,7 +37,7 @@ impl RedisValue {
     }
 }
 
-#[derive(Debug, serde::Deserialize, Clone)]
+#[derive(serde::Deserialize, Clone)]
 #[serde(default)]
 pub struct RedisSettings {
     pub host: String,
@@ -57,6 +60,72 @@ pub struct RedisSettings {
     pub default_command_timeout: u64,
     pub max_feed_count: u64,
     pub unresponsive_timeout: u64,
+    /// Route reads made through the replica helpers to replica nodes, applicable only in cluster
+    /// mode. Replication is asynchronous, so such reads may not reflect the most recent writes.
+    pub prefer_replica_reads: bool,
+    /// Environment tag (e.g. `staging`) prepended to every key ahead of the key prefix, to keep
+    /// data of different environments apart when they share a Redis instance
+    pub environment: Option<String>,
+    /// Policy applied once `max_in_flight_commands` is exceeded
+    pub backpressure_policy: RedisBackpressurePolicy,
+    /// Username used to authenticate with the host, and with every cluster node in cluster mode
+    pub username: Option<Secret<String>>,
+    /// Password used to authenticate with the host, and with every cluster node in cluster mode
+    pub password: Option<Secret<String>>,
+}
+
+/// Backpressure policy of the client, applied when the number of in-flight commands on a
+/// connection exceeds `max_in_flight_commands`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedisBackpressurePolicy {
+    /// Wait for the in-flight commands to complete before sending the next command
+    #[default]
+    Drain,
+    /// Delay each command by a duration proportional to the number of in-flight commands
+    Block,
+}
+
+impl From<RedisBackpressurePolicy> for fred::types::BackpressurePolicy {
+    fn from(policy: RedisBackpressurePolicy) -> Self {
+        match policy {
+            RedisBackpressurePolicy::Drain => Self::Drain,
+            RedisBackpressurePolicy::Block => Self::default_sleep(),
+        }
+    }
+}
+
+// The host and the cluster node addresses are masked, so that the configuration can be logged
+// safely; the credentials are masked by `Secret` itself
+impl std::fmt::Debug for RedisSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const MASKED: &str = "*** masked ***";
+
+        f.debug_struct("RedisSettings")
+            .field("host", &MASKED)
+            .field("port", &self.port)
+            .field("cluster_enabled", &self.cluster_enabled)
+            .field("cluster_urls", &vec![MASKED; self.cluster_urls.len()])
+            .field("use_legacy_version", &self.use_legacy_version)
+            .field("pool_size", &self.pool_size)
+            .field("reconnect_max_attempts", &self.reconnect_max_attempts)
+            .field("reconnect_delay", &self.reconnect_delay)
+            .field("default_ttl", &self.default_ttl)
+            .field("default_hash_ttl", &self.default_hash_ttl)
+            .field("stream_read_count", &self.stream_read_count)
+            .field("auto_pipeline", &self.auto_pipeline)
+            .field("disable_auto_backpressure", &self.disable_auto_backpressure)
+            .field("max_in_flight_commands", &self.max_in_flight_commands)
+            .field("default_command_timeout", &self.default_command_timeout)
+            .field("max_feed_count", &self.max_feed_count)
+            .field("unresponsive_timeout", &self.unresponsive_timeout)
+            .field("prefer_replica_reads", &self.prefer_replica_reads)
+            .field("environment", &self.environment)
+            .field("backpressure_policy", &self.backpressure_policy)
+            .field("username", &self.username)
+            .field("password", &self.password)
+            .finish()
+    }
 }
 
 impl RedisSettings {
@@ -108,6 +177,11 @@ impl Default for RedisSettings {
             default_command_timeout: 30,
             max_feed_count: 200,
             unresponsive_timeout: 10,
+            prefer_replica_reads: false,
+            environment: None,
+            backpressure_policy: RedisBackpressurePolicy::default(),
+            username: None,
+            password: None,
         }
     }
 }
@@ -283,3 +357,67 @@ impl fred::types::FromRedis for SaddReply {
         }
     }
 }
+
+/// State of the circuit breaker of a connector
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitState {
+    /// Requests flow to the connector as usual
+    Closed,
+    /// The connector failed too often recently, requests should not be sent to it
+    Open,
+    /// The open period elapsed, a trial request may be sent to probe whether the connector
+    /// recovered
+    HalfOpen,
+}
+
+/// Summary of the entries pending acknowledgement in a consumer group, as returned by `XPENDING`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PendingSummary {
+    pub total: u64,
+    /// Smallest id among the pending entries, `None` if there are no pending entries
+    pub min_id: Option<String>,
+    /// Greatest id among the pending entries, `None` if there are no pending entries
+    pub max_id: Option<String>,
+    /// Number of pending entries of each consumer that has at least one pending entry
+    pub consumers: HashMap<String, u64>,
+}
+
+impl fred::types::FromRedis for PendingSummary {
+    fn from_value(value: FredRedisValue) -> Result<Self, fred::error::RedisError> {
+        let parse_error = || {
+            fred::error::RedisError::new(
+                fred::error::RedisErrorKind::Parse,
+                "Unexpected XPENDING command reply",
+            )
+        };
+
+        let mut values = value.into_array().into_iter();
+        let (Some(total), Some(min_id), Some(max_id), Some(consumers), None) = (
+            values.next(),
+            values.next(),
+            values.next(),
+            values.next(),
+            values.next(),
+        ) else {
+            return Err(parse_error());
+        };
+
+        let consumers = crate::commands::redis_map_to_string_map(consumers)
+            .map_err(|_| parse_error())?
+            .into_iter()
+            .map(|(name, count)| {
+                count
+                    .parse::<u64>()
+                    .map(|count| (name, count))
+                    .map_err(|_| parse_error())
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            total: total.as_u64().ok_or_else(parse_error)?,
+            min_id: min_id.into_string(),
+            max_id: max_id.into_string(),
+            consumers,
+        })
+    }
+}
@@ -68,6 +68,7 @@ pub trait RouterData {
     fn get_billing_country(&self) -> Result<api_models::enums::CountryAlpha2, Error>;
     fn get_billing_phone(&self) -> Result<&api::PhoneDetails, Error>;
     fn get_description(&self) -> Result<String, Error>;
+    fn get_description_or_default(&self, default: &str) -> String;
     fn get_return_url(&self) -> Result<String, Error>;
     fn get_billing_address(&self) -> Result<&api::AddressDetails, Error>;
     fn get_shipping_address(&self) -> Result<&api::AddressDetails, Error>;
@@ -90,9 +91,11 @@ pub trait RouterData {
     fn get_payout_method_data(&self) -> Result<api::PayoutMethodData, Error>;
     #[cfg(feature = "payouts")]
     fn get_quote_id(&self) -> Result<String, Error>;
+    fn get_connector_request_reference_id(&self) -> Result<String, Error>;
 
     fn get_optional_billing(&self) -> Option<&api::Address>;
     fn get_optional_shipping(&self) -> Option<&api::Address>;
+    fn get_optional_shipping_with_phone(&self) -> Option<&api::Address>;
 
     fn get_optional_billing_full_name(&self) -> Option<Secret<String>>;
     fn get_optional_billing_line1(&self) -> Option<Secret<String>>;
@@ -104,7 +107,27 @@ pub trait RouterData {
     fn get_optional_billing_first_name(&self) -> Option<Secret<String>>;
     fn get_optional_billing_last_name(&self) -> Option<Secret<String>>;
     fn get_optional_billing_phone_number(&self) -> Option<Secret<String>>;
+    fn get_optional_billing_phone_country_code(&self) -> Option<String>;
     fn get_optional_billing_email(&self) -> Option<Email>;
+    fn get_contact_details(&self, fallback_to_shipping: bool) -> ContactDetails;
+    fn validate_billing_matches_issuer(&self, issuer_country: &str) -> Result<(), Error>;
+}
+
+/// Name, address, phone and email details of the customer, collected from a single address
+#[derive(Clone, Debug, Default)]
+pub struct ContactDetails {
+    pub first_name: Option<Secret<String>>,
+    pub last_name: Option<Secret<String>>,
+    pub line1: Option<Secret<String>>,
+    pub line2: Option<Secret<String>>,
+    pub line3: Option<Secret<String>>,
+    pub city: Option<String>,
+    pub state: Option<Secret<String>>,
+    pub zip: Option<Secret<String>>,
+    pub country: Option<enums::CountryAlpha2>,
+    pub phone_number: Option<Secret<String>>,
+    pub phone_country_code: Option<String>,
+    pub email: Option<Email>,
 }
 
 pub trait PaymentResponseRouterData {
@@ -155,6 +178,9 @@ where
 
 pub const SELECTED_PAYMENT_METHOD: &str = "Selected payment method";
 
+/// Maximum number of characters sent to connectors as the payment description
+pub const MAX_DESCRIPTION_LENGTH: usize = 255;
+
 pub fn get_unimplemented_payment_method_error_message(connector: &str) -> String {
     format!("{} through {}", SELECTED_PAYMENT_METHOD, connector)
 }
@@ -189,11 +215,30 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
         self.address.get_shipping()
     }
 
+    fn get_optional_shipping_with_phone(&self) -> Option<&api::Address> {
+        self.address.get_shipping().filter(|shipping| {
+            shipping
+                .phone
+                .as_ref()
+                .and_then(|phone| phone.number.as_ref())
+                .is_some()
+        })
+    }
+
     fn get_description(&self) -> Result<String, Error> {
         self.description
             .clone()
             .ok_or_else(missing_field_err("description"))
     }
+    fn get_description_or_default(&self, default: &str) -> String {
+        let description = self
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .unwrap_or_else(|| default.trim());
+        description.chars().take(MAX_DESCRIPTION_LENGTH).collect()
+    }
     fn get_return_url(&self) -> Result<String, Error> {
         self.return_url
             .clone()
@@ -348,11 +393,68 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
             })
     }
 
+    fn get_optional_billing_phone_country_code(&self) -> Option<String> {
+        self.address
+            .get_payment_method_billing()
+            .and_then(|billing_address| {
+                billing_address
+                    .phone
+                    .as_ref()
+                    .and_then(|phone_data| phone_data.country_code.clone())
+            })
+    }
+
     fn get_optional_billing_email(&self) -> Option<Email> {
         self.address
             .get_payment_method_billing()
             .and_then(|billing_address| billing_address.clone().email)
     }
+
+    fn get_contact_details(&self, fallback_to_shipping: bool) -> ContactDetails {
+        let address = self.get_optional_billing().or_else(|| {
+            self.get_optional_shipping()
+                .filter(|_| fallback_to_shipping)
+        });
+        let address_details = address.and_then(|address| address.address.as_ref());
+        let phone_details = address.and_then(|address| address.phone.as_ref());
+
+        ContactDetails {
+            first_name: address_details.and_then(|details| details.first_name.clone()),
+            last_name: address_details.and_then(|details| details.last_name.clone()),
+            line1: address_details.and_then(|details| details.line1.clone()),
+            line2: address_details.and_then(|details| details.line2.clone()),
+            line3: address_details.and_then(|details| details.line3.clone()),
+            city: address_details.and_then(|details| details.city.clone()),
+            state: address_details.and_then(|details| details.state.clone()),
+            zip: address_details.and_then(|details| details.zip.clone()),
+            country: address_details.and_then(|details| details.country),
+            phone_number: phone_details.and_then(|phone| phone.number.clone()),
+            phone_country_code: phone_details.and_then(|phone| phone.country_code.clone()),
+            email: address.and_then(|address| address.email.clone()),
+        }
+    }
+
+    /// Validates that the billing country matches the card issuer country (alpha-2 code) provided
+    /// by the connector. The check is skipped if either of the countries is not available.
+    fn validate_billing_matches_issuer(&self, issuer_country: &str) -> Result<(), Error> {
+        let issuer_country = issuer_country.trim();
+        match self.get_optional_billing_country() {
+            Some(billing_country)
+                if !issuer_country.is_empty()
+                    && !billing_country
+                        .to_string()
+                        .eq_ignore_ascii_case(issuer_country) =>
+            {
+                Err(errors::ConnectorError::InvalidDataFormat {
+                    field_name: "billing.address.country",
+                })
+                .attach_printable(format!(
+                    "billing country {billing_country} does not match card issuer country {issuer_country}"
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
     fn to_connector_meta<T>(&self) -> Result<T, Error>
     where
         T: serde::de::DeserializeOwned,
@@ -426,6 +528,121 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
             .to_owned()
             .ok_or_else(missing_field_err("quote_id"))
     }
+
+    /// Reference of the payment to be sent to the connector, falling back to the attempt id and
+    /// then the payment id when it is not set
+    fn get_connector_request_reference_id(&self) -> Result<String, Error> {
+        [
+            &self.connector_request_reference_id,
+            &self.attempt_id,
+            &self.payment_id,
+        ]
+        .into_iter()
+        .map(|reference_id| reference_id.trim())
+        .find(|reference_id| !reference_id.is_empty())
+        .map(ToString::to_string)
+        .ok_or_else(missing_field_err("connector_request_reference_id"))
+    }
+}
+
+pub trait RequestEmailData {
+    fn get_optional_email(&self) -> Option<Email>;
+}
+
+impl RequestEmailData for types::PaymentsAuthorizeData {
+    fn get_optional_email(&self) -> Option<Email> {
+        self.email.clone()
+    }
+}
+
+impl RequestEmailData for types::CompleteAuthorizeData {
+    fn get_optional_email(&self) -> Option<Email> {
+        self.email.clone()
+    }
+}
+
+impl RequestEmailData for types::PaymentsPreProcessingData {
+    fn get_optional_email(&self) -> Option<Email> {
+        self.email.clone()
+    }
+}
+
+impl RequestEmailData for types::SetupMandateRequestData {
+    fn get_optional_email(&self) -> Option<Email> {
+        self.email.clone()
+    }
+}
+
+impl RequestEmailData for types::ConnectorCustomerData {
+    fn get_optional_email(&self) -> Option<Email> {
+        self.email.clone()
+    }
+}
+
+pub trait RouterDataEmail {
+    /// Returns the billing email if present, else falls back to the email passed in the request
+    fn get_billing_or_request_email(&self) -> Option<Email>;
+    /// Returns the customer email passed in the request, independent of the billing address
+    fn get_optional_customer_email(&self) -> Option<Email>;
+}
+
+impl<Flow, Request, Response> RouterDataEmail for types::RouterData<Flow, Request, Response>
+where
+    Request: RequestEmailData,
+{
+    fn get_billing_or_request_email(&self) -> Option<Email> {
+        self.get_optional_billing_email()
+            .or_else(|| self.request.get_optional_email())
+    }
+
+    fn get_optional_customer_email(&self) -> Option<Email> {
+        self.request.get_optional_email()
+    }
+}
+
+pub trait RouterDataMetadata {
+    /// Returns the request metadata deep merged over the connector metadata, the request metadata
+    /// taking precedence on conflicting keys. Metadata that is not an object is ignored.
+    fn get_merged_metadata(&self) -> Option<pii::SecretSerdeValue>;
+}
+
+impl<Flow, Request, Response> RouterDataMetadata for types::RouterData<Flow, Request, Response>
+where
+    Request: PaymentsAuthorizeRequestData,
+{
+    fn get_merged_metadata(&self) -> Option<pii::SecretSerdeValue> {
+        let connector_metadata = self
+            .connector_meta_data
+            .clone()
+            .filter(|connector_metadata| connector_metadata.peek().is_object());
+        let request_metadata = self.request.get_metadata_as_object();
+        match (connector_metadata, request_metadata) {
+            (Some(connector_metadata), Some(request_metadata)) => {
+                let mut merged_metadata = connector_metadata.expose();
+                deep_merge_json(&mut merged_metadata, request_metadata.expose());
+                Some(Secret::new(merged_metadata))
+            }
+            (connector_metadata, request_metadata) => request_metadata.or(connector_metadata),
+        }
+    }
+}
+
+/// Merges `overlay` into `base`, recursing into objects present in both and replacing any other
+/// value of `base` with the one of `overlay`
+fn deep_merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(base_value) => deep_merge_json(base_value, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
 }
 
 pub trait PaymentsPreProcessingData {
@@ -547,12 +764,18 @@ pub trait PaymentsAuthorizeRequestData {
     fn connector_mandate_id(&self) -> Option<String>;
     fn is_mandate_payment(&self) -> bool;
     fn is_customer_initiated_mandate_payment(&self) -> bool;
+    fn is_merchant_initiated_mandate_payment(&self) -> bool;
     fn get_webhook_url(&self) -> Result<String, Error>;
+    fn get_webhook_url_or_default(&self, default_webhook_url: &str) -> String;
+    fn get_setup_future_usage(&self) -> Option<diesel_models::enums::FutureUsage>;
     fn get_router_return_url(&self) -> Result<String, Error>;
     fn is_wallet(&self) -> bool;
     fn is_card(&self) -> bool;
+    fn get_wallet_type(&self) -> Option<enums::PaymentMethodType>;
     fn get_payment_method_type(&self) -> Result<diesel_models::enums::PaymentMethodType, Error>;
     fn get_connector_mandate_id(&self) -> Result<String, Error>;
+    fn get_optional_network_transaction_id(&self) -> Option<String>;
+    fn get_network_txn_id_for_mit(&self) -> Result<String, Error>;
     fn get_complete_authorize_url(&self) -> Result<String, Error>;
     fn get_ip_address_as_optional(&self) -> Option<Secret<String, IpAddress>>;
     fn get_original_amount(&self) -> i64;
@@ -561,6 +784,8 @@ pub trait PaymentsAuthorizeRequestData {
     fn get_total_surcharge_amount(&self) -> Option<i64>;
     fn get_metadata_as_object(&self) -> Option<pii::SecretSerdeValue>;
     fn get_authentication_data(&self) -> Result<AuthenticationData, Error>;
+    fn get_cavv(&self) -> Result<Secret<String>, Error>;
+    fn get_eci(&self) -> Result<String, Error>;
 }
 
 pub trait PaymentMethodTokenizationRequestData {
@@ -638,6 +863,14 @@ impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
             .clone()
             .ok_or_else(missing_field_err("webhook_url"))
     }
+    fn get_webhook_url_or_default(&self, default_webhook_url: &str) -> String {
+        self.webhook_url
+            .clone()
+            .unwrap_or_else(|| default_webhook_url.to_string())
+    }
+    fn get_setup_future_usage(&self) -> Option<diesel_models::enums::FutureUsage> {
+        self.setup_future_usage
+    }
     fn get_router_return_url(&self) -> Result<String, Error> {
         self.router_return_url
             .clone()
@@ -652,6 +885,9 @@ impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
     fn is_card(&self) -> bool {
         matches!(self.payment_method_data, domain::PaymentMethodData::Card(_))
     }
+    fn get_wallet_type(&self) -> Option<enums::PaymentMethodType> {
+        derive_payment_method_type(&self.payment_method_data).filter(|_| self.is_wallet())
+    }
 
     fn get_payment_method_type(&self) -> Result<diesel_models::enums::PaymentMethodType, Error> {
         self.payment_method_type
@@ -663,6 +899,20 @@ impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
         self.connector_mandate_id()
             .ok_or_else(missing_field_err("connector_mandate_id"))
     }
+    fn get_optional_network_transaction_id(&self) -> Option<String> {
+        self.mandate_id
+            .as_ref()
+            .and_then(|mandate_ids| match &mandate_ids.mandate_reference_id {
+                Some(api_models::payments::MandateReferenceId::NetworkMandateId(
+                    network_transaction_id,
+                )) => Some(network_transaction_id.clone()),
+                _ => None,
+            })
+    }
+    fn get_network_txn_id_for_mit(&self) -> Result<String, Error> {
+        self.get_optional_network_transaction_id()
+            .ok_or_else(missing_field_err("mandate_id.network_transaction_id"))
+    }
     fn get_ip_address_as_optional(&self) -> Option<Secret<String, IpAddress>> {
         self.browser_info.clone().and_then(|browser_info| {
             browser_info
@@ -696,6 +946,15 @@ impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
         self.setup_mandate_details.is_some()
     }
 
+    fn is_merchant_initiated_mandate_payment(&self) -> bool {
+        self.mandate_id
+            .as_ref()
+            .and_then(|mandate_ids| mandate_ids.mandate_reference_id.as_ref())
+            .is_some()
+            && self.customer_acceptance.is_none()
+            && !self.is_customer_initiated_mandate_payment()
+    }
+
     fn get_metadata_as_object(&self) -> Option<pii::SecretSerdeValue> {
         self.metadata
             .clone()
@@ -714,6 +973,23 @@ impl PaymentsAuthorizeRequestData for types::PaymentsAuthorizeData {
             .clone()
             .ok_or_else(missing_field_err("authentication_data"))
     }
+    fn get_cavv(&self) -> Result<Secret<String>, Error> {
+        self.authentication_data
+            .as_ref()
+            .map(|authentication_data| authentication_data.cavv.trim())
+            .filter(|cavv| !cavv.is_empty())
+            .map(|cavv| Secret::new(cavv.to_string()))
+            .ok_or_else(missing_field_err("authentication_data.cavv"))
+    }
+    fn get_eci(&self) -> Result<String, Error> {
+        self.authentication_data
+            .as_ref()
+            .and_then(|authentication_data| authentication_data.eci.as_deref())
+            .map(str::trim)
+            .filter(|eci| !eci.is_empty())
+            .map(ToString::to_string)
+            .ok_or_else(missing_field_err("authentication_data.eci"))
+    }
 }
 
 pub trait ConnectorCustomerData {
@@ -729,6 +1005,7 @@ impl ConnectorCustomerData for types::ConnectorCustomerData {
 pub trait BrowserInformationData {
     fn get_accept_header(&self) -> Result<String, Error>;
     fn get_language(&self) -> Result<String, Error>;
+    fn get_preferred_languages(&self) -> Result<Vec<String>, Error>;
     fn get_screen_height(&self) -> Result<u32, Error>;
     fn get_screen_width(&self) -> Result<u32, Error>;
     fn get_color_depth(&self) -> Result<u8, Error>;
@@ -737,6 +1014,7 @@ pub trait BrowserInformationData {
     fn get_java_enabled(&self) -> Result<bool, Error>;
     fn get_java_script_enabled(&self) -> Result<bool, Error>;
     fn get_ip_address(&self) -> Result<Secret<String, IpAddress>, Error>;
+    fn get_parsed_ip(&self) -> Result<std::net::IpAddr, Error>;
 }
 
 impl BrowserInformationData for BrowserInformation {
@@ -746,6 +1024,12 @@ impl BrowserInformationData for BrowserInformation {
             .ok_or_else(missing_field_err("browser_info.ip_address"))?;
         Ok(Secret::new(ip_address.to_string()))
     }
+    // The address is parsed into an `IpAddr` when the browser information is deserialized, so a
+    // malformed value never reaches this point
+    fn get_parsed_ip(&self) -> Result<std::net::IpAddr, Error> {
+        self.ip_address
+            .ok_or_else(missing_field_err("browser_info.ip_address"))
+    }
     fn get_accept_header(&self) -> Result<String, Error> {
         self.accept_header
             .clone()
@@ -756,6 +1040,28 @@ impl BrowserInformationData for BrowserInformation {
             .clone()
             .ok_or_else(missing_field_err("browser_info.language"))
     }
+    fn get_preferred_languages(&self) -> Result<Vec<String>, Error> {
+        let language = self.get_language()?;
+        let mut weighted_languages = language
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.split(';');
+                let tag = parts.next()?.trim();
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |quality| quality.trim().parse::<f32>().ok())?;
+                (!tag.is_empty() && quality > 0.0).then(|| (tag.to_string(), quality))
+            })
+            .collect::<Vec<_>>();
+        if weighted_languages.is_empty() {
+            Err(errors::ConnectorError::InvalidDataFormat {
+                field_name: "browser_info.language",
+            })?
+        }
+        // stable sort keeps the original order for tags with the same quality
+        weighted_languages.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+        Ok(weighted_languages.into_iter().map(|(tag, _)| tag).collect())
+    }
     fn get_screen_height(&self) -> Result<u32, Error> {
         self.screen_height
             .ok_or_else(missing_field_err("browser_info.screen_height"))
@@ -792,6 +1098,7 @@ pub trait PaymentsCompleteAuthorizeRequestData {
     fn get_email(&self) -> Result<Email, Error>;
     fn get_redirect_response_payload(&self) -> Result<pii::SecretSerdeValue, Error>;
     fn get_complete_authorize_url(&self) -> Result<String, Error>;
+    fn get_setup_future_usage(&self) -> Option<diesel_models::enums::FutureUsage>;
 }
 
 impl PaymentsCompleteAuthorizeRequestData for types::CompleteAuthorizeData {
@@ -821,11 +1128,95 @@ impl PaymentsCompleteAuthorizeRequestData for types::CompleteAuthorizeData {
             .clone()
             .ok_or_else(missing_field_err("complete_authorize_url"))
     }
+    fn get_setup_future_usage(&self) -> Option<diesel_models::enums::FutureUsage> {
+        self.setup_future_usage
+    }
+}
+
+/// Builder for [`types::PaymentsResponseData::TransactionResponse`], with all the fields other
+/// than the resource id defaulting to `None`
+#[derive(Debug, Clone)]
+pub struct TransactionResponseBuilder {
+    resource_id: ResponseId,
+    redirection_data: Option<crate::services::RedirectForm>,
+    mandate_reference: Option<types::MandateReference>,
+    connector_metadata: Option<serde_json::Value>,
+    network_txn_id: Option<String>,
+    connector_response_reference_id: Option<String>,
+    incremental_authorization_allowed: Option<bool>,
+}
+
+impl TransactionResponseBuilder {
+    pub fn new(resource_id: ResponseId) -> Self {
+        Self {
+            resource_id,
+            redirection_data: None,
+            mandate_reference: None,
+            connector_metadata: None,
+            network_txn_id: None,
+            connector_response_reference_id: None,
+            incremental_authorization_allowed: None,
+        }
+    }
+
+    pub fn redirection_data(
+        mut self,
+        redirection_data: Option<crate::services::RedirectForm>,
+    ) -> Self {
+        self.redirection_data = redirection_data;
+        self
+    }
+
+    pub fn mandate_reference(mut self, mandate_reference: Option<types::MandateReference>) -> Self {
+        self.mandate_reference = mandate_reference;
+        self
+    }
+
+    pub fn connector_metadata(mut self, connector_metadata: Option<serde_json::Value>) -> Self {
+        self.connector_metadata = connector_metadata;
+        self
+    }
+
+    pub fn network_txn_id(mut self, network_txn_id: Option<String>) -> Self {
+        self.network_txn_id = network_txn_id;
+        self
+    }
+
+    pub fn connector_response_reference_id(
+        mut self,
+        connector_response_reference_id: Option<String>,
+    ) -> Self {
+        self.connector_response_reference_id = connector_response_reference_id;
+        self
+    }
+
+    pub fn incremental_authorization_allowed(
+        mut self,
+        incremental_authorization_allowed: Option<bool>,
+    ) -> Self {
+        self.incremental_authorization_allowed = incremental_authorization_allowed;
+        self
+    }
+
+    pub fn build(self) -> types::PaymentsResponseData {
+        types::PaymentsResponseData::TransactionResponse {
+            resource_id: self.resource_id,
+            redirection_data: self.redirection_data,
+            mandate_reference: self.mandate_reference,
+            connector_metadata: self.connector_metadata,
+            network_txn_id: self.network_txn_id,
+            connector_response_reference_id: self.connector_response_reference_id,
+            incremental_authorization_allowed: self.incremental_authorization_allowed,
+        }
+    }
 }
 
 pub trait PaymentsSyncRequestData {
     fn is_auto_capture(&self) -> Result<bool, Error>;
     fn get_connector_transaction_id(&self) -> CustomResult<String, errors::ConnectorError>;
+    fn get_connector_transaction_id_or_encoded(
+        &self,
+    ) -> CustomResult<String, errors::ConnectorError>;
 }
 
 impl PaymentsSyncRequestData for types::PaymentsSyncData {
@@ -846,6 +1237,23 @@ impl PaymentsSyncRequestData for types::PaymentsSyncData {
             .change_context(errors::ConnectorError::MissingConnectorTransactionID)?,
         }
     }
+
+    /// Same as `get_connector_transaction_id`, but also accepts the id in `EncodedData`, for
+    /// connectors which send back an encoded id
+    fn get_connector_transaction_id_or_encoded(
+        &self,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        match self.connector_transaction_id.clone() {
+            ResponseId::ConnectorTransactionId(txn_id) | ResponseId::EncodedData(txn_id) => {
+                Ok(txn_id)
+            }
+            ResponseId::NoResponseId => Err(errors::ValidationError::IncorrectValueProvided {
+                field_name: "connector_transaction_id",
+            })
+            .attach_printable("Expected connector transaction ID or encoded data not found")
+            .change_context(errors::ConnectorError::MissingConnectorTransactionID)?,
+        }
+    }
 }
 
 #[cfg(feature = "payouts")]
@@ -1027,6 +1435,29 @@ impl From<domain::GooglePayWalletData> for GooglePayWalletData {
     }
 }
 
+// Well known test card numbers published by the card networks and payment processors
+const TEST_CARD_NUMBERS: [&str; 13] = [
+    // Visa
+    "4111111111111111",
+    "4242424242424242",
+    "4012888888881881",
+    // Master
+    "5555555555554444",
+    "5105105105105100",
+    // AmericanExpress
+    "378282246310005",
+    "371449635398431",
+    // Discover
+    "6011111111111117",
+    "6011000990139424",
+    // DinersClub
+    "30569309025904",
+    "38520000023237",
+    // JCB
+    "3530111333300000",
+    "3566002020360505",
+];
+
 static CARD_REGEX: Lazy<HashMap<CardIssuer, Result<Regex, regex::Error>>> = Lazy::new(|| {
     let mut map = HashMap::new();
     // Reference: https://gist.github.com/michaelkeevildown/9096cd3aac9029c4e6e05588448a8841
@@ -1076,6 +1507,11 @@ pub trait CardData {
     fn get_expiry_date_as_yymm(&self) -> Result<Secret<String>, errors::ConnectorError>;
     fn get_expiry_month_as_i8(&self) -> Result<Secret<i8>, Error>;
     fn get_expiry_year_as_i32(&self) -> Result<Secret<i32>, Error>;
+    fn is_known_test_card(&self) -> bool;
+    fn get_expiry_month_2_digit(&self) -> Result<Secret<String>, Error>;
+    /// Whether the card expired before `now`, a card being valid through the end of its expiry
+    /// month
+    fn is_expired(&self, now: PrimitiveDateTime) -> Result<bool, Error>;
 }
 
 impl CardData for domain::Card {
@@ -1149,6 +1585,41 @@ impl CardData for domain::Card {
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)
             .map(Secret::new)
     }
+    fn is_known_test_card(&self) -> bool {
+        TEST_CARD_NUMBERS.contains(&self.card_number.peek().as_str())
+    }
+    fn get_expiry_month_2_digit(&self) -> Result<Secret<String>, Error> {
+        self.card_exp_month
+            .peek()
+            .trim()
+            .parse::<u8>()
+            .ok()
+            .filter(|month| (1..=12).contains(month))
+            .map(|month| Secret::new(format!("{month:02}")))
+            .ok_or_else(|| {
+                report!(errors::ConnectorError::InvalidDataFormat {
+                    field_name: "card_exp_month",
+                })
+            })
+    }
+    fn is_expired(&self, now: PrimitiveDateTime) -> Result<bool, Error> {
+        let expiry_month = self
+            .get_expiry_month_2_digit()?
+            .peek()
+            .parse::<u8>()
+            .change_context(errors::ConnectorError::InvalidDataFormat {
+                field_name: "card_exp_month",
+            })?;
+        let expiry_year = self
+            .get_expiry_year_4_digit()
+            .peek()
+            .trim()
+            .parse::<i32>()
+            .change_context(errors::ConnectorError::InvalidDataFormat {
+                field_name: "card_exp_year",
+            })?;
+        Ok((expiry_year, expiry_month) < (now.year(), u8::from(now.month())))
+    }
 }
 
 #[track_caller]
@@ -1165,6 +1636,106 @@ fn get_card_issuer(card_number: &str) -> Result<CardIssuer, Error> {
         errors::ConnectorError::NotImplemented("Card Type".into()),
     ))
 }
+
+/// Returns the explicitly provided card holder name, falling back to the billing full name.
+/// The domain card does not carry the holder name, hence it has to be passed by the caller.
+pub fn get_card_holder_name_with_fallback(
+    card_holder_name: Option<&Secret<String>>,
+    router_data: &impl RouterData,
+) -> Option<Secret<String>> {
+    card_holder_name
+        .filter(|card_holder_name| !card_holder_name.peek().trim().is_empty())
+        .cloned()
+        .or_else(|| router_data.get_optional_billing_full_name())
+}
+
+/// Splits a full name into first and last name for connectors which require them separately.
+/// The first word is taken as the first name and the remaining words as the last name, which is
+/// `None` for single word names.
+pub fn split_full_name(full_name: &Secret<String>) -> (Secret<String>, Option<Secret<String>>) {
+    let full_name = full_name.peek().trim();
+    match full_name.split_once(char::is_whitespace) {
+        Some((first_name, last_name)) => (
+            Secret::new(first_name.to_string()),
+            Some(Secret::new(last_name.trim().to_string())),
+        ),
+        None => (Secret::new(full_name.to_string()), None),
+    }
+}
+
+static POSTAL_CODE_REGEX: Lazy<HashMap<enums::CountryAlpha2, Result<Regex, regex::Error>>> =
+    Lazy::new(|| {
+        let mut map = HashMap::new();
+        map.insert(
+            enums::CountryAlpha2::US,
+            Regex::new(r"^[0-9]{5}(?:-?[0-9]{4})?$"),
+        );
+        map.insert(
+            enums::CountryAlpha2::CA,
+            Regex::new(r"^[A-Z][0-9][A-Z] ?[0-9][A-Z][0-9]$"),
+        );
+        map.insert(
+            enums::CountryAlpha2::GB,
+            Regex::new(r"^[A-Z]{1,2}[0-9][A-Z0-9]? ?[0-9][A-Z]{2}$"),
+        );
+        map
+    });
+
+/// Trims and uppercases the postal code, validating it against the format of the country.
+/// Postal codes of countries without a known format are only trimmed.
+pub fn normalize_postal_code(
+    country: enums::CountryAlpha2,
+    zip: &str,
+) -> Result<Secret<String>, Error> {
+    let zip = zip.trim();
+    let Some(regex) = POSTAL_CODE_REGEX.get(&country) else {
+        return Ok(Secret::new(zip.to_string()));
+    };
+    let regex = regex
+        .clone()
+        .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+    let zip = zip.to_uppercase();
+    if regex.is_match(&zip) {
+        Ok(Secret::new(zip))
+    } else {
+        Err(report!(errors::ConnectorError::InvalidDataFormat {
+            field_name: "billing.address.zip",
+        }))
+        .attach_printable(format!(
+            "postal code does not match the format of {country}"
+        ))
+    }
+}
+
+/// Computes a stable fingerprint of the card, the hex encoded HMAC-SHA256 of the card number and
+/// expiry with whitespace removed, keyed with the merchant salt
+pub fn compute_card_fingerprint(card: &domain::Card, merchant_salt: &[u8]) -> Secret<String> {
+    let normalize = |value: &str| value.split_whitespace().collect::<String>();
+    let fingerprint_data = format!(
+        "{}:{}/{}",
+        normalize(card.card_number.peek().as_str()),
+        normalize(card.card_exp_month.peek().as_str()),
+        normalize(card.card_exp_year.peek().as_str())
+    );
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, merchant_salt);
+    let tag = ring::hmac::sign(&key, fingerprint_data.as_bytes());
+    Secret::new(hex::encode(tag))
+}
+
+/// Returns every card issuer whose BIN range matches the card number, as some ranges overlap
+pub fn get_all_card_issuers(card_number: &str) -> Vec<CardIssuer> {
+    CARD_REGEX
+        .iter()
+        .filter_map(|(card_issuer, regex)| {
+            regex
+                .as_ref()
+                .ok()
+                .filter(|regex| regex.is_match(card_number))
+                .map(|_| *card_issuer)
+        })
+        .collect()
+}
+
 pub trait WalletData {
     fn get_wallet_token(&self) -> Result<Secret<String>, Error>;
     fn get_wallet_token_as_json<T>(&self, wallet_name: String) -> Result<T, Error>
@@ -1557,6 +2128,51 @@ where
     json.parse_value(std::any::type_name::<T>()).switch()
 }
 
+/// Same as [`to_connector_meta`], with the given context attached to the error to identify the
+/// metadata that failed to parse
+pub fn to_connector_meta_with_context<T>(
+    connector_meta: Option<serde_json::Value>,
+    context: &'static str,
+) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    to_connector_meta(connector_meta)
+        .attach_printable_lazy(|| format!("Failed to parse connector metadata: {context}"))
+}
+
+/// Same as [`to_connector_meta_from_secret`], with the given context attached to the error to
+/// identify the metadata that failed to parse
+pub fn to_connector_meta_from_secret_with_context<T>(
+    connector_meta: Option<Secret<serde_json::Value>>,
+    context: &'static str,
+) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    to_connector_meta_from_secret(connector_meta)
+        .attach_printable_lazy(|| format!("Failed to parse connector metadata: {context}"))
+}
+
+/// Serialize the given value into connector metadata, which can be read back with
+/// [`to_connector_meta_from_secret`]
+pub fn encode_connector_meta<T>(
+    value: &T,
+) -> CustomResult<pii::SecretSerdeValue, errors::ConnectorError>
+where
+    T: serde::Serialize,
+{
+    serde_json::to_value(value)
+        .map(Secret::new)
+        .change_context(errors::ConnectorError::RequestEncodingFailed)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed to encode {} as connector metadata",
+                std::any::type_name::<T>()
+            )
+        })
+}
+
 impl common_utils::errors::ErrorSwitch<errors::ConnectorError> for errors::ParsingError {
     fn switch(&self) -> errors::ConnectorError {
         errors::ConnectorError::ParsingFailed
@@ -1569,6 +2185,17 @@ pub fn base64_decode(data: String) -> Result<Vec<u8>, Error> {
         .change_context(errors::ConnectorError::ResponseDeserializationFailed)
 }
 
+/// Decodes a base64 blob stored in connector metadata, which may be encoded with either the
+/// standard or the URL-safe alphabet
+pub fn decode_base64_meta(value: &str) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+    let value = value.trim();
+    consts::BASE64_ENGINE
+        .decode(value)
+        .or_else(|_| consts::BASE64_ENGINE_URL_SAFE.decode(value))
+        .change_context(errors::ConnectorError::ResponseDeserializationFailed)
+        .attach_printable("Failed to decode base64 connector metadata")
+}
+
 pub fn to_currency_base_unit_from_optional_amount(
     amount: Option<i64>,
     currency: diesel_models::enums::Currency,
@@ -1615,15 +2242,49 @@ pub fn to_currency_base_unit(
     currency
         .to_currency_base_unit(amount)
         .change_context(errors::ConnectorError::ParsingFailed)
+        .attach_printable_lazy(|| {
+            format!("Failed to convert amount {amount} {currency} to base unit")
+        })
 }
 
+/// Strip surrounding whitespace and a leading byte order mark, with which some connectors prefix
+/// amounts in their responses
+pub fn sanitize_amount_string(amount: &str) -> &str {
+    amount.trim().trim_start_matches('\u{feff}').trim()
+}
+
+/// Convert an amount in major unit to the lowest denomination. Amounts in exponent notation
+/// like `"1.5e2"` are accepted, while non numeric values like `"NaN"` or `"inf"` are rejected.
 pub fn to_currency_lower_unit(
     amount: String,
     currency: diesel_models::enums::Currency,
 ) -> Result<String, error_stack::Report<errors::ConnectorError>> {
+    let sanitized_amount = sanitize_amount_string(&amount);
+    if sanitized_amount
+        .parse::<f64>()
+        .is_ok_and(|value| !value.is_finite())
+    {
+        return Err(report!(errors::ConnectorError::ResponseHandlingFailed)).attach_printable(
+            format!("Failed to convert amount {amount} {currency} to lower unit"),
+        );
+    }
+
     currency
-        .to_currency_lower_unit(amount)
+        .to_currency_lower_unit(sanitized_amount.to_string())
         .change_context(errors::ConnectorError::ResponseHandlingFailed)
+        .attach_printable_lazy(|| {
+            format!("Failed to convert amount {amount} {currency} to lower unit")
+        })
+}
+
+/// Compares two amounts given in major unit numerically, so that "10.0" and "10.00" are equal
+pub fn major_unit_amounts_equal(
+    amount: &str,
+    other_amount: &str,
+    currency: diesel_models::enums::Currency,
+) -> Result<bool, error_stack::Report<errors::ConnectorError>> {
+    Ok(to_currency_lower_unit(amount.to_string(), currency)?
+        == to_currency_lower_unit(other_amount.to_string(), currency)?)
 }
 
 pub fn construct_not_implemented_error_report(
@@ -1645,6 +2306,102 @@ pub fn construct_not_supported_error_report(
     .into()
 }
 
+/// Sums the amounts of all the order lines, each line amount being the unit amount times the
+/// quantity, for connectors accepting a single aggregated order line
+pub fn aggregate_order_amount(
+    order_details: &[OrderDetailsWithAmount],
+) -> CustomResult<i64, common_utils::errors::ParsingError> {
+    if order_details.is_empty() {
+        return Err(report!(
+            common_utils::errors::ParsingError::StructParseFailure("order_details")
+        ))
+        .attach_printable("Cannot aggregate the amount of empty order details");
+    }
+    order_details
+        .iter()
+        .try_fold(0_i64, |total_amount, order| {
+            order
+                .amount
+                .checked_mul(i64::from(order.quantity))
+                .and_then(|line_amount| total_amount.checked_add(line_amount))
+        })
+        .ok_or(common_utils::errors::ParsingError::IntegerOverflow(
+            "order_details amount",
+        ))
+        .attach_printable("Order amount overflowed while aggregating order details")
+}
+
+/// Trims the product names of the order lines and truncates them to `max_name_len` characters,
+/// for connectors rejecting product names longer than their field limit
+pub fn sanitize_order_details(
+    order_details: Vec<OrderDetailsWithAmount>,
+    max_name_len: usize,
+) -> Vec<OrderDetailsWithAmount> {
+    order_details
+        .into_iter()
+        .map(|order| OrderDetailsWithAmount {
+            product_name: order
+                .product_name
+                .trim()
+                .chars()
+                .take(max_name_len)
+                .collect::<String>()
+                .trim_end()
+                .to_string(),
+            ..order
+        })
+        .collect()
+}
+
+/// Validates that the connector request reference id fits within the maximum length accepted by
+/// the connector, returning the reference id unchanged if it does
+pub fn validate_reference_id_length(reference: &str, max_length: usize) -> Result<String, Error> {
+    let received_length = reference.chars().count();
+    if received_length > max_length {
+        Err(errors::ConnectorError::MaxFieldLengthViolated {
+            field_name: "connector_request_reference_id",
+            max_length,
+            received_length,
+        })?
+    }
+    Ok(reference.to_string())
+}
+
+/// Validates that the requested currency is one of the currencies supported by the connector
+pub fn validate_supported_currency(
+    supported_currencies: &[enums::Currency],
+    requested_currency: enums::Currency,
+    connector_name: &'static str,
+) -> Result<(), Error> {
+    if supported_currencies.contains(&requested_currency) {
+        Ok(())
+    } else {
+        Err(errors::ConnectorError::CurrencyNotSupported {
+            message: requested_currency.to_string(),
+            connector: connector_name,
+        }
+        .into())
+    }
+}
+
+/// Validates that the requested capture method, defaulting to automatic capture when not
+/// provided, is one of the capture methods supported by the connector
+pub fn validate_capture_method(
+    supported_capture_methods: &[enums::CaptureMethod],
+    requested_capture_method: Option<enums::CaptureMethod>,
+    connector_name: &'static str,
+) -> Result<(), Error> {
+    let capture_method = requested_capture_method.unwrap_or(enums::CaptureMethod::Automatic);
+    if supported_capture_methods.contains(&capture_method) {
+        Ok(())
+    } else {
+        Err(construct_not_supported_error_report(
+            capture_method,
+            connector_name,
+        ))
+    }
+}
+
 pub fn to_currency_base_unit_with_zero_decimal_check(
     amount: i64,
     currency: diesel_models::enums::Currency,
@@ -1652,6 +2409,9 @@ pub fn to_currency_base_unit_with_zero_decimal_check(
     currency
         .to_currency_base_unit_with_zero_decimal_check(amount)
         .change_context(errors::ConnectorError::RequestEncodingFailed)
+        .attach_printable_lazy(|| {
+            format!("Failed to convert amount {amount} {currency} to base unit")
+        })
 }
 
 pub fn to_currency_base_unit_asf64(
@@ -1661,6 +2421,9 @@ pub fn to_currency_base_unit_asf64(
     currency
         .to_currency_base_unit_asf64(amount)
         .change_context(errors::ConnectorError::ParsingFailed)
+        .attach_printable_lazy(|| {
+            format!("Failed to convert amount {amount} {currency} to base unit")
+        })
 }
 
 pub fn str_to_f32<S>(value: &str, serializer: S) -> Result<S::Ok, S::Error>
@@ -1865,6 +2628,79 @@ pub fn get_error_code_error_message_based_on_priority(
         .cloned()
 }
 
+/// Connector agnostic classification of a decline, used to group failures across connectors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NormalizedDeclineReason {
+    InsufficientFunds,
+    DoNotHonor,
+    ExpiredCard,
+    Fraud,
+    IncorrectCvc,
+    InvalidCardNumber,
+    LostOrStolenCard,
+    Other(String),
+}
+
+impl NormalizedDeclineReason {
+    /// Connector agnostic code of the reason, or `None` for [`Self::Other`], which only carries
+    /// the raw connector code
+    pub fn as_code(&self) -> Option<&'static str> {
+        match self {
+            Self::InsufficientFunds => Some("insufficient_funds"),
+            Self::DoNotHonor => Some("do_not_honor"),
+            Self::ExpiredCard => Some("expired_card"),
+            Self::Fraud => Some("fraud"),
+            Self::IncorrectCvc => Some("incorrect_cvc"),
+            Self::InvalidCardNumber => Some("invalid_card_number"),
+            Self::LostOrStolenCard => Some("lost_or_stolen_card"),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+/// Maps a connector decline code and message to a [`NormalizedDeclineReason`].
+/// ISO 8583 response codes and common string codes are matched first, then the message is
+/// searched for well known phrases. Anything else is returned as `Other` with the raw code.
+pub fn classify_decline(code: &str, message: &str) -> NormalizedDeclineReason {
+    let normalized_code = code.trim().to_lowercase();
+    let reason = match normalized_code.as_str() {
+        "51" | "insufficient_funds" => Some(NormalizedDeclineReason::InsufficientFunds),
+        "05" | "do_not_honor" | "do_not_honour" => Some(NormalizedDeclineReason::DoNotHonor),
+        "33" | "54" | "expired_card" => Some(NormalizedDeclineReason::ExpiredCard),
+        "34" | "59" | "fraudulent" | "suspected_fraud" => Some(NormalizedDeclineReason::Fraud),
+        "82" | "n7" | "incorrect_cvc" | "invalid_cvc" => {
+            Some(NormalizedDeclineReason::IncorrectCvc)
+        }
+        "14" | "incorrect_number" | "invalid_number" => {
+            Some(NormalizedDeclineReason::InvalidCardNumber)
+        }
+        "41" | "43" | "lost_card" | "stolen_card" => {
+            Some(NormalizedDeclineReason::LostOrStolenCard)
+        }
+        _ => None,
+    };
+    reason.unwrap_or_else(|| {
+        let message = message.to_lowercase();
+        if message.contains("insufficient") {
+            NormalizedDeclineReason::InsufficientFunds
+        } else if message.contains("do not honor") || message.contains("do not honour") {
+            NormalizedDeclineReason::DoNotHonor
+        } else if message.contains("expired") {
+            NormalizedDeclineReason::ExpiredCard
+        } else if message.contains("fraud") {
+            NormalizedDeclineReason::Fraud
+        } else if message.contains("cvc") || message.contains("cvv") {
+            NormalizedDeclineReason::IncorrectCvc
+        } else if message.contains("lost") || message.contains("stolen") {
+            NormalizedDeclineReason::LostOrStolenCard
+        } else if message.contains("card number") {
+            NormalizedDeclineReason::InvalidCardNumber
+        } else {
+            NormalizedDeclineReason::Other(code.to_string())
+        }
+    })
+}
+
 pub trait MultipleCaptureSyncResponse {
     fn get_connector_capture_id(&self) -> String;
     fn get_capture_attempt_status(&self) -> enums::AttemptStatus;
@@ -1936,6 +2772,22 @@ pub fn get_timestamp_in_milliseconds(datetime: &PrimitiveDateTime) -> i64 {
     utc_datetime.unix_timestamp() * 1000
 }
 
+/// Parse an RFC 3339 timestamp returned by a connector, with or without fractional seconds and
+/// with either `Z` or an offset, into a UTC date time
+pub fn parse_connector_timestamp(timestamp: &str) -> Result<PrimitiveDateTime, Error> {
+    let utc_datetime = time::OffsetDateTime::parse(
+        timestamp.trim(),
+        &time::format_description::well_known::Rfc3339,
+    )
+    .change_context(errors::ConnectorError::ResponseDeserializationFailed)
+    .attach_printable_lazy(|| format!("Failed to parse connector timestamp {timestamp}"))?
+    .to_offset(time::UtcOffset::UTC);
+    Ok(PrimitiveDateTime::new(
+        utc_datetime.date(),
+        utc_datetime.time(),
+    ))
+}
+
 #[cfg(feature = "frm")]
 pub trait FraudCheckSaleRequest {
     fn get_order_details(&self) -> Result<Vec<OrderDetailsWithAmount>, Error>;
@@ -2064,6 +2916,40 @@ impl FrmTransactionRouterDataRequest for fraud_check::FrmTransactionRouterData {
     }
 }
 
+/// Maps the payment statuses commonly returned by connectors to the attempt status, ignoring case
+/// and treating `-` and spaces as `_`. An authorized payment is pending capture when captured
+/// automatically. Returns `None` for statuses specific to a connector, which it has to map itself.
+pub fn get_attempt_status_from_connector_status(
+    connector_status: &str,
+    capture_method: Option<enums::CaptureMethod>,
+) -> Option<enums::AttemptStatus> {
+    let connector_status = connector_status
+        .trim()
+        .to_lowercase()
+        .replace(['-', ' '], "_");
+    match connector_status.as_str() {
+        "authorized" | "authorised" => match capture_method {
+            Some(enums::CaptureMethod::Automatic) | None => Some(enums::AttemptStatus::Pending),
+            Some(enums::CaptureMethod::Manual)
+            | Some(enums::CaptureMethod::ManualMultiple)
+            | Some(enums::CaptureMethod::Scheduled) => Some(enums::AttemptStatus::Authorized),
+        },
+        "succeeded" | "success" | "successful" | "captured" | "paid" | "completed" => {
+            Some(enums::AttemptStatus::Charged)
+        }
+        "partially_captured" => Some(enums::AttemptStatus::PartialCharged),
+        "pending" | "processing" | "in_progress" => Some(enums::AttemptStatus::Pending),
+        "requires_action" | "pending_authentication" => {
+            Some(enums::AttemptStatus::AuthenticationPending)
+        }
+        "failed" | "failure" | "declined" | "rejected" | "refused" => {
+            Some(enums::AttemptStatus::Failure)
+        }
+        "cancelled" | "canceled" | "voided" => Some(enums::AttemptStatus::Voided),
+        _ => None,
+    }
+}
+
 pub fn is_payment_failure(status: enums::AttemptStatus) -> bool {
     match status {
         common_enums::AttemptStatus::AuthenticationFailed
@@ -2150,6 +3036,179 @@ pub fn get_card_details(
     }
 }
 
+/// Derive the payment method type from the payment method data.
+/// Returns `None` for cards of unknown type and for data that does not map to a single type.
+pub fn derive_payment_method_type(
+    payment_method_data: &domain::PaymentMethodData,
+) -> Option<enums::PaymentMethodType> {
+    match payment_method_data {
+        domain::PaymentMethodData::Card(card) => card.card_type.as_deref().and_then(|card_type| {
+            match card_type.to_lowercase().as_str() {
+                "credit" => Some(enums::PaymentMethodType::Credit),
+                "debit" => Some(enums::PaymentMethodType::Debit),
+                _ => None,
+            }
+        }),
+        domain::PaymentMethodData::CardRedirect(card_redirect_data) => {
+            Some(match card_redirect_data {
+                domain::CardRedirectData::Knet {} => enums::PaymentMethodType::Knet,
+                domain::CardRedirectData::Benefit {} => enums::PaymentMethodType::Benefit,
+                domain::CardRedirectData::MomoAtm {} => enums::PaymentMethodType::MomoAtm,
+                domain::CardRedirectData::CardRedirect {} => enums::PaymentMethodType::CardRedirect,
+            })
+        }
+        domain::PaymentMethodData::Wallet(wallet_data) => Some(match wallet_data {
+            domain::WalletData::AliPayQr(_) | domain::WalletData::AliPayRedirect(_) => {
+                enums::PaymentMethodType::AliPay
+            }
+            domain::WalletData::AliPayHkRedirect(_) => enums::PaymentMethodType::AliPayHk,
+            domain::WalletData::MomoRedirect(_) => enums::PaymentMethodType::Momo,
+            domain::WalletData::KakaoPayRedirect(_) => enums::PaymentMethodType::KakaoPay,
+            domain::WalletData::GoPayRedirect(_) => enums::PaymentMethodType::GoPay,
+            domain::WalletData::GcashRedirect(_) => enums::PaymentMethodType::Gcash,
+            domain::WalletData::ApplePay(_)
+            | domain::WalletData::ApplePayRedirect(_)
+            | domain::WalletData::ApplePayThirdPartySdk(_) => enums::PaymentMethodType::ApplePay,
+            domain::WalletData::DanaRedirect {} => enums::PaymentMethodType::Dana,
+            domain::WalletData::GooglePay(_)
+            | domain::WalletData::GooglePayRedirect(_)
+            | domain::WalletData::GooglePayThirdPartySdk(_) => enums::PaymentMethodType::GooglePay,
+            domain::WalletData::MbWayRedirect(_) => enums::PaymentMethodType::MbWay,
+            domain::WalletData::MobilePayRedirect(_) => enums::PaymentMethodType::MobilePay,
+            domain::WalletData::PaypalRedirect(_) | domain::WalletData::PaypalSdk(_) => {
+                enums::PaymentMethodType::Paypal
+            }
+            domain::WalletData::SamsungPay(_) => enums::PaymentMethodType::SamsungPay,
+            domain::WalletData::TwintRedirect {} => enums::PaymentMethodType::Twint,
+            domain::WalletData::VippsRedirect {} => enums::PaymentMethodType::Vipps,
+            domain::WalletData::TouchNGoRedirect(_) => enums::PaymentMethodType::TouchNGo,
+            domain::WalletData::WeChatPayRedirect(_) | domain::WalletData::WeChatPayQr(_) => {
+                enums::PaymentMethodType::WeChatPay
+            }
+            domain::WalletData::CashappQr(_) => enums::PaymentMethodType::Cashapp,
+            domain::WalletData::SwishQr(_) => enums::PaymentMethodType::Swish,
+        }),
+        domain::PaymentMethodData::PayLater(pay_later_data) => Some(match pay_later_data {
+            domain::PayLaterData::KlarnaRedirect { .. }
+            | domain::PayLaterData::KlarnaSdk { .. } => enums::PaymentMethodType::Klarna,
+            domain::PayLaterData::AffirmRedirect {} => enums::PaymentMethodType::Affirm,
+            domain::PayLaterData::AfterpayClearpayRedirect { .. } => {
+                enums::PaymentMethodType::AfterpayClearpay
+            }
+            domain::PayLaterData::PayBrightRedirect {} => enums::PaymentMethodType::PayBright,
+            domain::PayLaterData::WalleyRedirect {} => enums::PaymentMethodType::Walley,
+            domain::PayLaterData::AlmaRedirect {} => enums::PaymentMethodType::Alma,
+            domain::PayLaterData::AtomeRedirect {} => enums::PaymentMethodType::Atome,
+        }),
+        domain::PaymentMethodData::BankRedirect(bank_redirect_data) => {
+            Some(match bank_redirect_data {
+                domain::BankRedirectData::BancontactCard { .. } => {
+                    enums::PaymentMethodType::BancontactCard
+                }
+                domain::BankRedirectData::Bizum {} => enums::PaymentMethodType::Bizum,
+                domain::BankRedirectData::Blik { .. } => enums::PaymentMethodType::Blik,
+                domain::BankRedirectData::Eps { .. } => enums::PaymentMethodType::Eps,
+                domain::BankRedirectData::Giropay { .. } => enums::PaymentMethodType::Giropay,
+                domain::BankRedirectData::Ideal { .. } => enums::PaymentMethodType::Ideal,
+                domain::BankRedirectData::Interac { .. } => enums::PaymentMethodType::Interac,
+                domain::BankRedirectData::OnlineBankingCzechRepublic { .. } => {
+                    enums::PaymentMethodType::OnlineBankingCzechRepublic
+                }
+                domain::BankRedirectData::OnlineBankingFinland { .. } => {
+                    enums::PaymentMethodType::OnlineBankingFinland
+                }
+                domain::BankRedirectData::OnlineBankingPoland { .. } => {
+                    enums::PaymentMethodType::OnlineBankingPoland
+                }
+                domain::BankRedirectData::OnlineBankingSlovakia { .. } => {
+                    enums::PaymentMethodType::OnlineBankingSlovakia
+                }
+                domain::BankRedirectData::OpenBankingUk { .. } => {
+                    enums::PaymentMethodType::OpenBankingUk
+                }
+                domain::BankRedirectData::Przelewy24 { .. } => enums::PaymentMethodType::Przelewy24,
+                domain::BankRedirectData::Sofort { .. } => enums::PaymentMethodType::Sofort,
+                domain::BankRedirectData::Trustly { .. } => enums::PaymentMethodType::Trustly,
+                domain::BankRedirectData::OnlineBankingFpx { .. } => {
+                    enums::PaymentMethodType::OnlineBankingFpx
+                }
+                domain::BankRedirectData::OnlineBankingThailand { .. } => {
+                    enums::PaymentMethodType::OnlineBankingThailand
+                }
+            })
+        }
+        domain::PaymentMethodData::BankDebit(bank_debit_data) => Some(match bank_debit_data {
+            domain::BankDebitData::AchBankDebit { .. } => enums::PaymentMethodType::Ach,
+            domain::BankDebitData::SepaBankDebit { .. } => enums::PaymentMethodType::Sepa,
+            domain::BankDebitData::BecsBankDebit { .. } => enums::PaymentMethodType::Becs,
+            domain::BankDebitData::BacsBankDebit { .. } => enums::PaymentMethodType::Bacs,
+        }),
+        domain::PaymentMethodData::BankTransfer(bank_transfer_data) => {
+            Some(match bank_transfer_data.as_ref() {
+                domain::BankTransferData::AchBankTransfer { .. } => enums::PaymentMethodType::Ach,
+                domain::BankTransferData::SepaBankTransfer { .. } => enums::PaymentMethodType::Sepa,
+                domain::BankTransferData::BacsBankTransfer { .. } => enums::PaymentMethodType::Bacs,
+                domain::BankTransferData::MultibancoBankTransfer { .. } => {
+                    enums::PaymentMethodType::Multibanco
+                }
+                domain::BankTransferData::PermataBankTransfer { .. } => {
+                    enums::PaymentMethodType::PermataBankTransfer
+                }
+                domain::BankTransferData::BcaBankTransfer { .. } => {
+                    enums::PaymentMethodType::BcaBankTransfer
+                }
+                domain::BankTransferData::BniVaBankTransfer { .. } => {
+                    enums::PaymentMethodType::BniVa
+                }
+                domain::BankTransferData::BriVaBankTransfer { .. } => {
+                    enums::PaymentMethodType::BriVa
+                }
+                domain::BankTransferData::CimbVaBankTransfer { .. } => {
+                    enums::PaymentMethodType::CimbVa
+                }
+                domain::BankTransferData::DanamonVaBankTransfer { .. } => {
+                    enums::PaymentMethodType::DanamonVa
+                }
+                domain::BankTransferData::MandiriVaBankTransfer { .. } => {
+                    enums::PaymentMethodType::MandiriVa
+                }
+                domain::BankTransferData::Pix {} => enums::PaymentMethodType::Pix,
+                domain::BankTransferData::Pse {} => enums::PaymentMethodType::Pse,
+                domain::BankTransferData::LocalBankTransfer { .. } => {
+                    enums::PaymentMethodType::LocalBankTransfer
+                }
+            })
+        }
+        domain::PaymentMethodData::Crypto(_) => Some(enums::PaymentMethodType::CryptoCurrency),
+        domain::PaymentMethodData::Upi(_) => Some(enums::PaymentMethodType::UpiCollect),
+        domain::PaymentMethodData::Voucher(voucher_data) => Some(match voucher_data {
+            domain::VoucherData::Boleto(_) => enums::PaymentMethodType::Boleto,
+            domain::VoucherData::Efecty => enums::PaymentMethodType::Efecty,
+            domain::VoucherData::PagoEfectivo => enums::PaymentMethodType::PagoEfectivo,
+            domain::VoucherData::RedCompra => enums::PaymentMethodType::RedCompra,
+            domain::VoucherData::RedPagos => enums::PaymentMethodType::RedPagos,
+            domain::VoucherData::Alfamart(_) => enums::PaymentMethodType::Alfamart,
+            domain::VoucherData::Indomaret(_) => enums::PaymentMethodType::Indomaret,
+            domain::VoucherData::Oxxo => enums::PaymentMethodType::Oxxo,
+            domain::VoucherData::SevenEleven(_) => enums::PaymentMethodType::SevenEleven,
+            domain::VoucherData::Lawson(_) => enums::PaymentMethodType::Lawson,
+            domain::VoucherData::MiniStop(_) => enums::PaymentMethodType::MiniStop,
+            domain::VoucherData::FamilyMart(_) => enums::PaymentMethodType::FamilyMart,
+            domain::VoucherData::Seicomart(_) => enums::PaymentMethodType::Seicomart,
+            domain::VoucherData::PayEasy(_) => enums::PaymentMethodType::PayEasy,
+        }),
+        domain::PaymentMethodData::GiftCard(gift_card_data) => {
+            Some(match gift_card_data.as_ref() {
+                domain::GiftCardData::Givex(_) => enums::PaymentMethodType::Givex,
+                domain::GiftCardData::PaySafeCard {} => enums::PaymentMethodType::PaySafeCard,
+            })
+        }
+        domain::PaymentMethodData::MandatePayment
+        | domain::PaymentMethodData::Reward
+        | domain::PaymentMethodData::CardToken(_) => None,
+    }
+}
+
 #[cfg(test)]
 mod error_code_error_message_tests {
     #![allow(clippy::unwrap_used)]
@@ -2229,3 +3288,1614 @@ mod error_code_error_message_tests {
         assert_eq!(error_code_error_message_none, None);
     }
 }
+
+#[cfg(test)]
+mod decline_reason_tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_decline_by_code() {
+        assert_eq!(
+            classify_decline("51", "Declined"),
+            NormalizedDeclineReason::InsufficientFunds
+        );
+        assert_eq!(
+            classify_decline("05", "Declined"),
+            NormalizedDeclineReason::DoNotHonor
+        );
+        assert_eq!(
+            classify_decline("EXPIRED_CARD", ""),
+            NormalizedDeclineReason::ExpiredCard
+        );
+        assert_eq!(
+            classify_decline("fraudulent", ""),
+            NormalizedDeclineReason::Fraud
+        );
+        assert_eq!(
+            classify_decline("N7", ""),
+            NormalizedDeclineReason::IncorrectCvc
+        );
+        assert_eq!(
+            classify_decline("stolen_card", ""),
+            NormalizedDeclineReason::LostOrStolenCard
+        );
+    }
+
+    #[test]
+    fn test_classify_decline_by_message() {
+        assert_eq!(
+            classify_decline("card_declined", "Your card has insufficient funds."),
+            NormalizedDeclineReason::InsufficientFunds
+        );
+        assert_eq!(
+            classify_decline("2000", "Do Not Honor"),
+            NormalizedDeclineReason::DoNotHonor
+        );
+        assert_eq!(
+            classify_decline("E101", "Card expired"),
+            NormalizedDeclineReason::ExpiredCard
+        );
+    }
+
+    #[test]
+    fn test_classify_decline_unknown() {
+        assert_eq!(
+            classify_decline("R99", "Something went wrong"),
+            NormalizedDeclineReason::Other("R99".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalized_decline_reason_as_code() {
+        assert_eq!(
+            classify_decline("51", "Declined").as_code(),
+            Some("insufficient_funds")
+        );
+        assert_eq!(
+            classify_decline("R99", "Something went wrong").as_code(),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod amount_conversion_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_to_currency_base_unit() {
+        assert_eq!(
+            to_currency_base_unit(1000, enums::Currency::USD).unwrap(),
+            "10.00"
+        );
+    }
+
+    #[test]
+    fn test_to_currency_base_unit_attaches_context_on_overflow() {
+        let amount = i64::from(u32::MAX) + 1;
+
+        let error = to_currency_base_unit(amount, enums::Currency::USD).unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::ParsingFailed
+        );
+        let error_message = format!("{error:?}");
+        assert!(error_message.contains("out of range integral type conversion attempted"));
+        assert!(error_message.contains(&format!(
+            "Failed to convert amount {amount} USD to base unit"
+        )));
+    }
+
+    #[test]
+    fn test_to_currency_lower_unit_attaches_context_on_parse_failure() {
+        let error = to_currency_lower_unit("ten".to_string(), enums::Currency::USD).unwrap_err();
+
+        let error_message = format!("{error:?}");
+        assert!(error_message.contains("invalid float literal"));
+        assert!(error_message.contains("Failed to convert amount ten USD to lower unit"));
+    }
+
+    #[test]
+    fn test_to_currency_lower_unit_with_padded_amount() {
+        assert_eq!(
+            to_currency_lower_unit(" 10.00 ".to_string(), enums::Currency::USD).unwrap(),
+            "1000"
+        );
+        assert_eq!(
+            to_currency_lower_unit("\u{feff}10.00".to_string(), enums::Currency::USD).unwrap(),
+            "1000"
+        );
+    }
+
+    #[test]
+    fn test_to_currency_lower_unit_with_exponent_notation() {
+        assert_eq!(
+            to_currency_lower_unit("1.5e2".to_string(), enums::Currency::USD).unwrap(),
+            "15000"
+        );
+        assert_eq!(
+            to_currency_lower_unit("1.5E-1".to_string(), enums::Currency::USD).unwrap(),
+            "15"
+        );
+        assert!(major_unit_amounts_equal("1.5e2", "150", enums::Currency::USD).unwrap());
+    }
+
+    #[test]
+    fn test_to_currency_lower_unit_with_malformed_exponent() {
+        for amount in ["1.5e", "1.5e2e3", "NaN", "inf"] {
+            let error =
+                to_currency_lower_unit(amount.to_string(), enums::Currency::USD).unwrap_err();
+            assert_eq!(
+                error.current_context(),
+                &errors::ConnectorError::ResponseHandlingFailed
+            );
+        }
+    }
+
+    #[test]
+    fn test_major_unit_amounts_equal_with_trailing_zeros() {
+        assert!(major_unit_amounts_equal("10.0", "10.00", enums::Currency::USD).unwrap());
+        assert!(major_unit_amounts_equal("1.5", "1.500", enums::Currency::BHD).unwrap());
+    }
+
+    #[test]
+    fn test_major_unit_amounts_equal_with_mismatch() {
+        assert!(!major_unit_amounts_equal("10.00", "10.01", enums::Currency::USD).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod router_data_tests {
+    #![allow(clippy::unwrap_used)]
+    use std::{marker::PhantomData, str::FromStr};
+
+    use super::*;
+
+    fn get_card() -> domain::Card {
+        domain::Card {
+            card_number: cards::CardNumber::from_str("4111111111111111").unwrap(),
+            card_exp_month: Secret::new("10".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_cvc: Secret::new("123".to_string()),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        }
+    }
+
+    fn get_authorize_data() -> types::PaymentsAuthorizeData {
+        types::PaymentsAuthorizeData {
+            payment_method_data: domain::PaymentMethodData::Card(get_card()),
+            amount: 1000,
+            email: None,
+            customer_name: None,
+            currency: enums::Currency::USD,
+            confirm: true,
+            statement_descriptor_suffix: None,
+            statement_descriptor: None,
+            capture_method: None,
+            router_return_url: None,
+            webhook_url: None,
+            complete_authorize_url: None,
+            setup_future_usage: None,
+            mandate_id: None,
+            off_session: None,
+            customer_acceptance: None,
+            setup_mandate_details: None,
+            browser_info: None,
+            order_details: None,
+            order_category: None,
+            session_token: None,
+            enrolled_for_3ds: false,
+            related_transaction_id: None,
+            payment_experience: None,
+            payment_method_type: None,
+            surcharge_details: None,
+            customer_id: None,
+            request_incremental_authorization: false,
+            metadata: None,
+            authentication_data: None,
+        }
+    }
+
+    fn get_complete_authorize_data() -> types::CompleteAuthorizeData {
+        types::CompleteAuthorizeData {
+            payment_method_data: Some(domain::PaymentMethodData::Card(get_card())),
+            amount: 1000,
+            email: None,
+            currency: enums::Currency::USD,
+            confirm: true,
+            statement_descriptor_suffix: None,
+            capture_method: None,
+            setup_future_usage: None,
+            mandate_id: None,
+            off_session: None,
+            setup_mandate_details: None,
+            redirect_response: None,
+            browser_info: None,
+            connector_transaction_id: None,
+            connector_meta: None,
+            complete_authorize_url: None,
+            metadata: None,
+        }
+    }
+
+    fn get_router_data<Request>(
+        request: Request,
+        address: types::PaymentAddress,
+    ) -> types::RouterData<api::Authorize, Request, types::PaymentsResponseData> {
+        types::RouterData {
+            flow: PhantomData,
+            merchant_id: "merchant_1".to_string(),
+            customer_id: None,
+            connector_customer: None,
+            connector: "dummy".to_string(),
+            payment_id: "pay_1".to_string(),
+            attempt_id: "pay_1_1".to_string(),
+            status: enums::AttemptStatus::default(),
+            payment_method: enums::PaymentMethod::Card,
+            connector_auth_type: types::ConnectorAuthType::default(),
+            description: None,
+            return_url: None,
+            address,
+            auth_type: enums::AuthenticationType::NoThreeDs,
+            connector_meta_data: None,
+            amount_captured: None,
+            access_token: None,
+            session_token: None,
+            reference_id: None,
+            payment_method_token: None,
+            recurring_mandate_payment_data: None,
+            preprocessing_id: None,
+            payment_method_balance: None,
+            connector_api_version: None,
+            request,
+            response: Err(types::ErrorResponse::default()),
+            connector_request_reference_id: "pay_1_1".to_string(),
+            #[cfg(feature = "payouts")]
+            payout_method_data: None,
+            #[cfg(feature = "payouts")]
+            quote_id: None,
+            test_mode: None,
+            connector_http_status_code: None,
+            external_latency: None,
+            apple_pay_flow: None,
+            frm_metadata: None,
+            dispute_id: None,
+            refund_id: None,
+            connector_response: None,
+            payment_method_status: None,
+        }
+    }
+
+    fn get_billing_address(email: Option<&str>) -> types::PaymentAddress {
+        types::PaymentAddress::new(
+            None,
+            Some(api::Address {
+                address: None,
+                phone: None,
+                email: email.map(|email| Email::from_str(email).unwrap()),
+            }),
+            None,
+        )
+    }
+
+    fn get_full_address() -> api::Address {
+        api::Address {
+            address: Some(api::AddressDetails {
+                city: Some("San Francisco".to_string()),
+                country: Some(enums::CountryAlpha2::US),
+                line1: Some(Secret::new("1467".to_string())),
+                line2: Some(Secret::new("Harrison Street".to_string())),
+                line3: Some(Secret::new("Harrison Street".to_string())),
+                zip: Some(Secret::new("94122".to_string())),
+                state: Some(Secret::new("California".to_string())),
+                first_name: Some(Secret::new("John".to_string())),
+                last_name: Some(Secret::new("Doe".to_string())),
+            }),
+            phone: Some(api::PhoneDetails {
+                number: Some(Secret::new("9123456789".to_string())),
+                country_code: Some("+1".to_string()),
+            }),
+            email: Some(Email::from_str("john.doe@example.com").unwrap()),
+        }
+    }
+
+    fn get_mandate_ids() -> api_models::payments::MandateIds {
+        api_models::payments::MandateIds {
+            mandate_id: Some("man_1".to_string()),
+            mandate_reference_id: Some(api_models::payments::MandateReferenceId::NetworkMandateId(
+                "network_txn_1".to_string(),
+            )),
+        }
+    }
+
+    #[test]
+    fn test_get_contact_details_from_billing() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(get_full_address()), None),
+        );
+
+        let contact_details = router_data.get_contact_details(false);
+        assert_eq!(
+            contact_details.first_name.map(|name| name.expose()),
+            Some("John".to_string())
+        );
+        assert_eq!(
+            contact_details.last_name.map(|name| name.expose()),
+            Some("Doe".to_string())
+        );
+        assert_eq!(
+            contact_details.line1.map(|line| line.expose()),
+            Some("1467".to_string())
+        );
+        assert_eq!(contact_details.city, Some("San Francisco".to_string()));
+        assert_eq!(contact_details.country, Some(enums::CountryAlpha2::US));
+        assert_eq!(
+            contact_details.zip.map(|zip| zip.expose()),
+            Some("94122".to_string())
+        );
+        assert_eq!(
+            contact_details.phone_number.map(|number| number.expose()),
+            Some("9123456789".to_string())
+        );
+        assert_eq!(contact_details.phone_country_code, Some("+1".to_string()));
+        assert_eq!(
+            contact_details.email,
+            Some(Email::from_str("john.doe@example.com").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_get_contact_details_for_minimal_address() {
+        let minimal_address = api::Address {
+            address: Some(api::AddressDetails {
+                country: Some(enums::CountryAlpha2::DE),
+                ..Default::default()
+            }),
+            phone: None,
+            email: None,
+        };
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(minimal_address), None),
+        );
+
+        let contact_details = router_data.get_contact_details(true);
+        assert_eq!(contact_details.country, Some(enums::CountryAlpha2::DE));
+        assert!(contact_details.first_name.is_none());
+        assert!(contact_details.line1.is_none());
+        assert!(contact_details.city.is_none());
+        assert!(contact_details.phone_number.is_none());
+        assert!(contact_details.email.is_none());
+    }
+
+    #[test]
+    fn test_get_contact_details_with_shipping_fallback() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(Some(get_full_address()), None, None),
+        );
+
+        assert!(router_data.get_contact_details(false).first_name.is_none());
+        assert_eq!(
+            router_data
+                .get_contact_details(true)
+                .first_name
+                .map(|name| name.expose()),
+            Some("John".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_billing_or_request_email_prefers_billing_email() {
+        let mut request = get_authorize_data();
+        request.email = Some(Email::from_str("request@example.com").unwrap());
+        let router_data =
+            get_router_data(request, get_billing_address(Some("billing@example.com")));
+
+        assert_eq!(
+            router_data.get_billing_or_request_email(),
+            Some(Email::from_str("billing@example.com").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_get_billing_or_request_email_falls_back_to_request_email() {
+        let mut request = get_authorize_data();
+        request.email = Some(Email::from_str("request@example.com").unwrap());
+        let router_data = get_router_data(request, get_billing_address(None));
+
+        assert_eq!(
+            router_data.get_billing_or_request_email(),
+            Some(Email::from_str("request@example.com").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_get_billing_or_request_email_when_both_are_absent() {
+        let router_data = get_router_data(get_authorize_data(), types::PaymentAddress::default());
+
+        assert_eq!(router_data.get_billing_or_request_email(), None);
+    }
+
+    #[test]
+    fn test_get_optional_shipping_with_phone() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(Some(get_full_address()), None, None),
+        );
+
+        assert_eq!(
+            router_data
+                .get_optional_shipping_with_phone()
+                .and_then(|shipping| shipping.phone.as_ref())
+                .and_then(|phone| phone.number.clone())
+                .map(|number| number.expose()),
+            Some("9123456789".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_optional_shipping_with_phone_when_phone_is_absent() {
+        let shipping_address = api::Address {
+            phone: None,
+            ..get_full_address()
+        };
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(Some(shipping_address), None, None),
+        );
+
+        assert!(router_data.get_optional_shipping().is_some());
+        assert!(router_data.get_optional_shipping_with_phone().is_none());
+    }
+
+    #[test]
+    fn test_get_optional_shipping_with_phone_when_shipping_is_absent() {
+        let router_data = get_router_data(get_authorize_data(), types::PaymentAddress::default());
+
+        assert!(router_data.get_optional_shipping().is_none());
+        assert!(router_data.get_optional_shipping_with_phone().is_none());
+    }
+
+    #[test]
+    fn test_get_optional_billing_phone_country_code() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(get_full_address()), None),
+        );
+
+        assert_eq!(
+            router_data.get_optional_billing_phone_country_code(),
+            Some("+1".to_string())
+        );
+        assert_eq!(
+            router_data
+                .get_optional_billing_phone_number()
+                .map(|number| number.expose()),
+            Some("9123456789".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_optional_billing_phone_country_code_without_phone() {
+        let router_data = get_router_data(get_authorize_data(), get_billing_address(None));
+
+        assert_eq!(router_data.get_optional_billing_phone_country_code(), None);
+    }
+
+    #[test]
+    fn test_validate_billing_matches_issuer() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(get_full_address()), None),
+        );
+
+        assert!(router_data.validate_billing_matches_issuer("US").is_ok());
+        assert!(router_data.validate_billing_matches_issuer("us").is_ok());
+    }
+
+    #[test]
+    fn test_validate_billing_matches_issuer_mismatch() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(get_full_address()), None),
+        );
+
+        let error = router_data
+            .validate_billing_matches_issuer("DE")
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::InvalidDataFormat {
+                field_name: "billing.address.country"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_validate_billing_matches_issuer_when_data_is_missing() {
+        let router_data_without_billing =
+            get_router_data(get_authorize_data(), types::PaymentAddress::default());
+        assert!(router_data_without_billing
+            .validate_billing_matches_issuer("DE")
+            .is_ok());
+
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(get_full_address()), None),
+        );
+        assert!(router_data.validate_billing_matches_issuer("").is_ok());
+    }
+
+    #[test]
+    fn test_get_card_holder_name_with_fallback_prefers_card_holder_name() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(get_full_address()), None),
+        );
+        let card_holder_name = Secret::new("Jane Roe".to_string());
+
+        let name = get_card_holder_name_with_fallback(Some(&card_holder_name), &router_data);
+        assert_eq!(name.map(|name| name.expose()), Some("Jane Roe".to_string()));
+    }
+
+    #[test]
+    fn test_get_card_holder_name_with_fallback_to_billing_name() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            types::PaymentAddress::new(None, Some(get_full_address()), None),
+        );
+        let empty_card_holder_name = Secret::new(" ".to_string());
+
+        let name = get_card_holder_name_with_fallback(None, &router_data);
+        assert_eq!(name.map(|name| name.expose()), Some("John Doe".to_string()));
+
+        let name = get_card_holder_name_with_fallback(Some(&empty_card_holder_name), &router_data);
+        assert_eq!(name.map(|name| name.expose()), Some("John Doe".to_string()));
+    }
+
+    #[test]
+    fn test_get_card_holder_name_with_fallback_without_names() {
+        let router_data = get_router_data(get_authorize_data(), get_billing_address(None));
+
+        assert!(get_card_holder_name_with_fallback(None, &router_data).is_none());
+    }
+
+    #[test]
+    fn test_split_full_name_with_first_and_last_name() {
+        let (first_name, last_name) = split_full_name(&Secret::new("Ada Lovelace".to_string()));
+        assert_eq!(first_name.peek(), "Ada");
+        assert_eq!(last_name.unwrap().peek(), "Lovelace");
+    }
+
+    #[test]
+    fn test_split_full_name_with_single_name() {
+        let (first_name, last_name) = split_full_name(&Secret::new(" Cher ".to_string()));
+        assert_eq!(first_name.peek(), "Cher");
+        assert!(last_name.is_none());
+    }
+
+    #[test]
+    fn test_split_full_name_with_middle_name() {
+        let (first_name, last_name) =
+            split_full_name(&Secret::new("Augusta Ada  King".to_string()));
+        assert_eq!(first_name.peek(), "Augusta");
+        assert_eq!(last_name.unwrap().peek(), "Ada  King");
+    }
+
+    #[test]
+    fn test_normalize_postal_code_with_us_zip_plus_four() {
+        let zip = normalize_postal_code(enums::CountryAlpha2::US, " 94107-1234 ").unwrap();
+        assert_eq!(zip.peek(), "94107-1234");
+        let zip = normalize_postal_code(enums::CountryAlpha2::CA, "k1a 0b1").unwrap();
+        assert_eq!(zip.peek(), "K1A 0B1");
+    }
+
+    #[test]
+    fn test_normalize_postal_code_with_malformed_gb_postcode() {
+        let error = normalize_postal_code(enums::CountryAlpha2::GB, "SW1A 1A").unwrap_err();
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::InvalidDataFormat {
+                field_name: "billing.address.zip",
+            }
+        );
+        assert!(normalize_postal_code(enums::CountryAlpha2::GB, "sw1a 1aa").is_ok());
+    }
+
+    #[test]
+    fn test_normalize_postal_code_with_unknown_country() {
+        let zip = normalize_postal_code(enums::CountryAlpha2::NL, " 1234 ab ").unwrap();
+        assert_eq!(zip.peek(), "1234 ab");
+    }
+
+    #[test]
+    fn test_get_connector_request_reference_id_falls_back_to_attempt_id() {
+        let mut router_data =
+            get_router_data(get_authorize_data(), types::PaymentAddress::default());
+        assert_eq!(
+            router_data.get_connector_request_reference_id().unwrap(),
+            "pay_1_1"
+        );
+
+        router_data.connector_request_reference_id = "merchant_order_1".to_string();
+        assert_eq!(
+            router_data.get_connector_request_reference_id().unwrap(),
+            "merchant_order_1"
+        );
+
+        router_data.connector_request_reference_id = " ".to_string();
+        router_data.attempt_id = "pay_1_2".to_string();
+        assert_eq!(
+            router_data.get_connector_request_reference_id().unwrap(),
+            "pay_1_2"
+        );
+
+        router_data.attempt_id = String::new();
+        assert_eq!(
+            router_data.get_connector_request_reference_id().unwrap(),
+            "pay_1"
+        );
+    }
+
+    #[test]
+    fn test_get_connector_request_reference_id_without_any_reference() {
+        let mut router_data =
+            get_router_data(get_authorize_data(), types::PaymentAddress::default());
+        router_data.connector_request_reference_id = String::new();
+        router_data.attempt_id = " ".to_string();
+        router_data.payment_id = String::new();
+
+        let error = router_data
+            .get_connector_request_reference_id()
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "connector_request_reference_id"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_validate_reference_id_length_at_boundary() {
+        let reference = "a".repeat(35);
+
+        assert_eq!(
+            validate_reference_id_length(&reference, 35).unwrap(),
+            reference
+        );
+    }
+
+    #[test]
+    fn test_validate_reference_id_length_over_boundary() {
+        let reference = "a".repeat(36);
+
+        let error = validate_reference_id_length(&reference, 35).unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::MaxFieldLengthViolated {
+                field_name: "connector_request_reference_id",
+                max_length: 35,
+                received_length: 36,
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_connector_transaction_id_or_encoded() {
+        let mut sync_data = types::PaymentsSyncData {
+            connector_transaction_id: ResponseId::ConnectorTransactionId("txn_1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            sync_data.get_connector_transaction_id_or_encoded().unwrap(),
+            "txn_1"
+        );
+        assert_eq!(sync_data.get_connector_transaction_id().unwrap(), "txn_1");
+
+        sync_data.connector_transaction_id = ResponseId::EncodedData("encoded_txn".to_string());
+        assert_eq!(
+            sync_data.get_connector_transaction_id_or_encoded().unwrap(),
+            "encoded_txn"
+        );
+        assert!(sync_data.get_connector_transaction_id().is_err());
+
+        sync_data.connector_transaction_id = ResponseId::NoResponseId;
+        assert_eq!(
+            sync_data
+                .get_connector_transaction_id_or_encoded()
+                .unwrap_err()
+                .current_context(),
+            &errors::ConnectorError::MissingConnectorTransactionID
+        );
+    }
+
+    #[test]
+    fn test_get_description_or_default_present() {
+        let mut router_data =
+            get_router_data(get_authorize_data(), types::PaymentAddress::default());
+        router_data.description = Some("  Order #42  ".to_string());
+        assert_eq!(
+            router_data.get_description_or_default("Payment for merchant"),
+            "Order #42"
+        );
+    }
+
+    #[test]
+    fn test_get_description_or_default_fallback() {
+        let mut router_data =
+            get_router_data(get_authorize_data(), types::PaymentAddress::default());
+        router_data.description = None;
+        assert_eq!(
+            router_data.get_description_or_default("Payment for merchant"),
+            "Payment for merchant"
+        );
+        router_data.description = Some("   ".to_string());
+        assert_eq!(
+            router_data.get_description_or_default("Payment for merchant"),
+            "Payment for merchant"
+        );
+    }
+
+    #[test]
+    fn test_get_description_or_default_capped() {
+        let mut router_data =
+            get_router_data(get_authorize_data(), types::PaymentAddress::default());
+        router_data.description = Some("a".repeat(MAX_DESCRIPTION_LENGTH + 10));
+        assert_eq!(
+            router_data
+                .get_description_or_default("Payment for merchant")
+                .len(),
+            MAX_DESCRIPTION_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_is_merchant_initiated_mandate_payment_for_cit() {
+        let mut request = get_authorize_data();
+        request.setup_mandate_details = Some(hyperswitch_domain_models::mandates::MandateData {
+            update_mandate_id: None,
+            customer_acceptance: None,
+            mandate_type: None,
+        });
+
+        assert!(request.is_customer_initiated_mandate_payment());
+        assert!(!request.is_merchant_initiated_mandate_payment());
+    }
+
+    #[test]
+    fn test_is_merchant_initiated_mandate_payment_for_mit() {
+        let mut request = get_authorize_data();
+        request.mandate_id = Some(get_mandate_ids());
+
+        assert!(!request.is_customer_initiated_mandate_payment());
+        assert!(request.is_merchant_initiated_mandate_payment());
+    }
+
+    #[test]
+    fn test_is_merchant_initiated_mandate_payment_for_one_off_payment() {
+        let request = get_authorize_data();
+
+        assert!(!request.is_mandate_payment());
+        assert!(!request.is_merchant_initiated_mandate_payment());
+    }
+
+    #[test]
+    fn test_get_network_txn_id_for_mit() {
+        let mut request = get_authorize_data();
+        request.mandate_id = Some(get_mandate_ids());
+
+        assert_eq!(
+            request.get_network_txn_id_for_mit().unwrap(),
+            "network_txn_1".to_string()
+        );
+    }
+
+    #[test]
+    fn test_get_network_txn_id_for_mit_when_absent() {
+        let mut request = get_authorize_data();
+        request.mandate_id = Some(api_models::payments::MandateIds {
+            mandate_id: Some("man_1".to_string()),
+            mandate_reference_id: Some(
+                api_models::payments::MandateReferenceId::ConnectorMandateId(
+                    api_models::payments::ConnectorMandateReferenceId {
+                        connector_mandate_id: Some("connector_mandate_1".to_string()),
+                        payment_method_id: None,
+                        update_history: None,
+                    },
+                ),
+            ),
+        });
+
+        assert!(request.get_optional_network_transaction_id().is_none());
+        let error = request.get_network_txn_id_for_mit().unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "mandate_id.network_transaction_id"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_get_setup_future_usage() {
+        for setup_future_usage in [
+            Some(enums::FutureUsage::OnSession),
+            Some(enums::FutureUsage::OffSession),
+            None,
+        ] {
+            let authorize_data = types::PaymentsAuthorizeData {
+                setup_future_usage,
+                ..get_authorize_data()
+            };
+            let complete_authorize_data = types::CompleteAuthorizeData {
+                setup_future_usage,
+                ..get_complete_authorize_data()
+            };
+
+            assert_eq!(authorize_data.get_setup_future_usage(), setup_future_usage);
+            assert_eq!(
+                complete_authorize_data.get_setup_future_usage(),
+                setup_future_usage
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_optional_customer_email_ignores_billing_email() {
+        let mut request = get_authorize_data();
+        request.email = Some(Email::from_str("customer@example.com").unwrap());
+        let router_data =
+            get_router_data(request, get_billing_address(Some("billing@example.com")));
+
+        assert_eq!(
+            router_data.get_optional_customer_email(),
+            Some(Email::from_str("customer@example.com").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_get_optional_customer_email_when_absent() {
+        let router_data = get_router_data(
+            get_authorize_data(),
+            get_billing_address(Some("billing@example.com")),
+        );
+
+        assert_eq!(router_data.get_optional_customer_email(), None);
+    }
+
+    #[test]
+    fn test_get_webhook_url_or_default_with_request_webhook_url() {
+        let authorize_data = types::PaymentsAuthorizeData {
+            webhook_url: Some("https://merchant.example.com/webhooks".to_string()),
+            ..get_authorize_data()
+        };
+
+        assert_eq!(
+            authorize_data.get_webhook_url_or_default("https://router.example.com/webhooks"),
+            "https://merchant.example.com/webhooks"
+        );
+    }
+
+    #[test]
+    fn test_get_webhook_url_or_default_falls_back_to_default() {
+        let authorize_data = get_authorize_data();
+
+        assert_eq!(
+            authorize_data.get_webhook_url_or_default("https://router.example.com/webhooks"),
+            "https://router.example.com/webhooks"
+        );
+    }
+
+    #[test]
+    fn test_get_cavv_and_eci_with_authentication_data() {
+        let authorize_data = types::PaymentsAuthorizeData {
+            authentication_data: Some(AuthenticationData {
+                eci: Some("05".to_string()),
+                cavv: "AAABBEg0VhI0VniQEjRWAAAAAAA=".to_string(),
+                threeds_server_transaction_id: "threeds_server_transaction_id".to_string(),
+                message_version: "2.2.0".to_string(),
+            }),
+            ..get_authorize_data()
+        };
+
+        assert_eq!(
+            authorize_data.get_cavv().unwrap().peek(),
+            "AAABBEg0VhI0VniQEjRWAAAAAAA="
+        );
+        assert_eq!(authorize_data.get_eci().unwrap(), "05");
+    }
+
+    #[test]
+    fn test_get_cavv_and_eci_without_authentication_data() {
+        let authorize_data = get_authorize_data();
+
+        assert_eq!(
+            authorize_data.get_cavv().unwrap_err().current_context(),
+            &errors::ConnectorError::MissingRequiredField {
+                field_name: "authentication_data.cavv"
+            }
+        );
+        assert_eq!(
+            authorize_data.get_eci().unwrap_err().current_context(),
+            &errors::ConnectorError::MissingRequiredField {
+                field_name: "authentication_data.eci"
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_wallet_type() {
+        let paypal = types::PaymentsAuthorizeData {
+            payment_method_data: domain::PaymentMethodData::Wallet(
+                domain::WalletData::PaypalRedirect(domain::PaypalRedirection { email: None }),
+            ),
+            ..get_authorize_data()
+        };
+        assert_eq!(
+            paypal.get_wallet_type(),
+            Some(enums::PaymentMethodType::Paypal)
+        );
+
+        let mb_way = types::PaymentsAuthorizeData {
+            payment_method_data: domain::PaymentMethodData::Wallet(
+                domain::WalletData::MbWayRedirect(Box::new(domain::MbWayRedirection {})),
+            ),
+            ..get_authorize_data()
+        };
+        assert_eq!(
+            mb_way.get_wallet_type(),
+            Some(enums::PaymentMethodType::MbWay)
+        );
+
+        assert_eq!(get_authorize_data().get_wallet_type(), None);
+    }
+
+    #[test]
+    fn test_validate_capture_method_supported() {
+        let supported = [
+            enums::CaptureMethod::Automatic,
+            enums::CaptureMethod::Manual,
+        ];
+
+        assert!(
+            validate_capture_method(&supported, Some(enums::CaptureMethod::Manual), "dummy")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validate_capture_method_unsupported() {
+        let supported = [enums::CaptureMethod::Automatic];
+
+        let error = validate_capture_method(
+            &supported,
+            Some(enums::CaptureMethod::ManualMultiple),
+            "dummy",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::NotSupported {
+                message: enums::CaptureMethod::ManualMultiple.to_string(),
+                connector: "dummy",
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_capture_method_defaults_to_automatic() {
+        assert!(validate_capture_method(&[enums::CaptureMethod::Automatic], None, "dummy").is_ok());
+        assert!(validate_capture_method(&[enums::CaptureMethod::Manual], None, "dummy").is_err());
+    }
+
+    #[test]
+    fn test_validate_supported_currency_supported() {
+        let supported = [enums::Currency::USD, enums::Currency::EUR];
+
+        assert!(validate_supported_currency(&supported, enums::Currency::EUR, "dummy").is_ok());
+    }
+
+    #[test]
+    fn test_validate_supported_currency_unsupported() {
+        let supported = [enums::Currency::USD, enums::Currency::EUR];
+
+        let error =
+            validate_supported_currency(&supported, enums::Currency::JPY, "dummy").unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::CurrencyNotSupported {
+                message: "JPY".to_string(),
+                connector: "dummy",
+            }
+        );
+    }
+
+    #[test]
+    fn test_aggregate_order_amount_sums_order_lines() {
+        let order_details = vec![
+            OrderDetailsWithAmount {
+                product_name: "shirt".to_string(),
+                quantity: 2,
+                amount: 1500,
+                ..Default::default()
+            },
+            OrderDetailsWithAmount {
+                product_name: "socks".to_string(),
+                quantity: 3,
+                amount: 250,
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(aggregate_order_amount(&order_details).unwrap(), 3750);
+    }
+
+    #[test]
+    fn test_aggregate_order_amount_rejects_empty_and_overflowing_order_details() {
+        assert!(matches!(
+            aggregate_order_amount(&[]).unwrap_err().current_context(),
+            common_utils::errors::ParsingError::StructParseFailure("order_details")
+        ));
+
+        let order_details = vec![
+            OrderDetailsWithAmount {
+                quantity: 1,
+                amount: i64::MAX,
+                ..Default::default()
+            },
+            OrderDetailsWithAmount {
+                quantity: 1,
+                amount: 1,
+                ..Default::default()
+            },
+        ];
+        assert!(matches!(
+            aggregate_order_amount(&order_details)
+                .unwrap_err()
+                .current_context(),
+            common_utils::errors::ParsingError::IntegerOverflow("order_details amount")
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_order_details_truncates_product_names() {
+        let order_details = vec![
+            OrderDetailsWithAmount {
+                product_name: "  Extra long product name exceeding the limit  ".to_string(),
+                quantity: 2,
+                amount: 1500,
+                ..Default::default()
+            },
+            OrderDetailsWithAmount {
+                product_name: "socks ".to_string(),
+                quantity: 3,
+                amount: 250,
+                ..Default::default()
+            },
+        ];
+
+        let sanitized = sanitize_order_details(order_details, 18);
+
+        assert_eq!(
+            sanitized
+                .iter()
+                .map(|order| (order.product_name.as_str(), order.quantity, order.amount))
+                .collect::<Vec<_>>(),
+            vec![("Extra long product", 2, 1500), ("socks", 3, 250)]
+        );
+    }
+
+    #[test]
+    fn test_get_preferred_languages_sorted_by_quality() {
+        let browser_info = types::BrowserInformation {
+            language: Some("fr;q=0.5, en-US,en;q=0.9,de;q=0.5".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            browser_info.get_preferred_languages().unwrap(),
+            vec!["en-US", "en", "fr", "de"]
+        );
+    }
+
+    #[test]
+    fn test_get_preferred_languages_skips_unacceptable() {
+        let browser_info = types::BrowserInformation {
+            language: Some("en-GB,*;q=0".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            browser_info.get_preferred_languages().unwrap(),
+            vec!["en-GB"]
+        );
+        let browser_info = types::BrowserInformation::default();
+        assert!(browser_info.get_preferred_languages().is_err());
+    }
+
+    #[test]
+    fn test_get_parsed_ip() {
+        let browser_info: types::BrowserInformation =
+            serde_json::from_value(serde_json::json!({ "ip_address": "203.0.113.7" })).unwrap();
+        assert_eq!(
+            browser_info.get_parsed_ip().unwrap(),
+            std::net::IpAddr::V4(std::net::Ipv4Addr::new(203, 0, 113, 7))
+        );
+
+        let browser_info: types::BrowserInformation =
+            serde_json::from_value(serde_json::json!({ "ip_address": "2001:db8::1" })).unwrap();
+        assert!(browser_info.get_parsed_ip().unwrap().is_ipv6());
+    }
+
+    #[test]
+    fn test_get_parsed_ip_malformed_or_missing() {
+        let malformed = serde_json::from_value::<types::BrowserInformation>(
+            serde_json::json!({ "ip_address": "203.0.113.256" }),
+        );
+        assert!(malformed.is_err());
+
+        let browser_info = types::BrowserInformation::default();
+        assert!(browser_info.get_parsed_ip().is_err());
+    }
+
+    #[test]
+    fn test_transaction_response_builder_minimal() {
+        let response =
+            TransactionResponseBuilder::new(ResponseId::ConnectorTransactionId("txn_1".into()))
+                .build();
+
+        assert!(matches!(
+            response,
+            types::PaymentsResponseData::TransactionResponse {
+                resource_id: ResponseId::ConnectorTransactionId(ref txn_id),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+                incremental_authorization_allowed: None,
+            } if txn_id == "txn_1"
+        ));
+    }
+
+    #[test]
+    fn test_transaction_response_builder_fully_populated() {
+        let response =
+            TransactionResponseBuilder::new(ResponseId::ConnectorTransactionId("txn_1".into()))
+                .redirection_data(Some(crate::services::RedirectForm::Form {
+                    endpoint: "https://example.com/3ds".to_string(),
+                    method: crate::services::Method::Post,
+                    form_fields: HashMap::new(),
+                }))
+                .mandate_reference(Some(types::MandateReference {
+                    connector_mandate_id: Some("mandate_1".to_string()),
+                    payment_method_id: None,
+                }))
+                .connector_metadata(Some(serde_json::json!({ "key": "value" })))
+                .network_txn_id(Some("network_txn_1".to_string()))
+                .connector_response_reference_id(Some("reference_1".to_string()))
+                .incremental_authorization_allowed(Some(true))
+                .build();
+
+        assert!(matches!(
+            response,
+            types::PaymentsResponseData::TransactionResponse {
+                redirection_data: Some(crate::services::RedirectForm::Form { .. }),
+                mandate_reference: Some(types::MandateReference {
+                    connector_mandate_id: Some(ref mandate_id),
+                    ..
+                }),
+                connector_metadata: Some(ref connector_metadata),
+                network_txn_id: Some(ref network_txn_id),
+                connector_response_reference_id: Some(ref reference_id),
+                incremental_authorization_allowed: Some(true),
+                ..
+            } if mandate_id == "mandate_1"
+                && connector_metadata == &serde_json::json!({ "key": "value" })
+                && network_txn_id == "network_txn_1"
+                && reference_id == "reference_1"
+        ));
+    }
+
+    #[test]
+    fn test_get_attempt_status_from_connector_status_for_authorized() {
+        assert_eq!(
+            get_attempt_status_from_connector_status(
+                "authorized",
+                Some(enums::CaptureMethod::Manual)
+            ),
+            Some(enums::AttemptStatus::Authorized)
+        );
+        assert_eq!(
+            get_attempt_status_from_connector_status(
+                "Authorized",
+                Some(enums::CaptureMethod::Automatic)
+            ),
+            Some(enums::AttemptStatus::Pending)
+        );
+        assert_eq!(
+            get_attempt_status_from_connector_status("AUTHORISED", None),
+            Some(enums::AttemptStatus::Pending)
+        );
+    }
+
+    #[test]
+    fn test_get_attempt_status_from_connector_status_for_common_and_unknown_statuses() {
+        assert_eq!(
+            get_attempt_status_from_connector_status("partially-captured", None),
+            Some(enums::AttemptStatus::PartialCharged)
+        );
+        assert_eq!(
+            get_attempt_status_from_connector_status(" Declined ", None),
+            Some(enums::AttemptStatus::Failure)
+        );
+        assert_eq!(
+            get_attempt_status_from_connector_status("awaiting_settlement", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_connector_timestamp() {
+        assert_eq!(
+            parse_connector_timestamp("2024-03-05T10:15:30Z").unwrap(),
+            time::macros::datetime!(2024-03-05 10:15:30)
+        );
+        assert_eq!(
+            parse_connector_timestamp("2024-03-05T10:15:30.123456Z").unwrap(),
+            time::macros::datetime!(2024-03-05 10:15:30.123456)
+        );
+        assert_eq!(
+            parse_connector_timestamp("2024-03-05T15:45:30+05:30").unwrap(),
+            time::macros::datetime!(2024-03-05 10:15:30)
+        );
+    }
+
+    #[test]
+    fn test_parse_connector_timestamp_malformed() {
+        let error = parse_connector_timestamp("2024-03-05 10:15").unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::ResponseDeserializationFailed
+        );
+    }
+
+    #[test]
+    fn test_get_merged_metadata_with_overlapping_keys() {
+        let request = types::PaymentsAuthorizeData {
+            metadata: Some(Secret::new(serde_json::json!({
+                "merchant_reference": "request_reference",
+                "nested": { "request_only": 1, "shared": "request" }
+            }))),
+            ..get_authorize_data()
+        };
+        let mut router_data = get_router_data(request, types::PaymentAddress::default());
+        router_data.connector_meta_data = Some(Secret::new(serde_json::json!({
+            "merchant_reference": "connector_reference",
+            "nested": { "connector_only": 2, "shared": "connector" }
+        })));
+
+        assert_eq!(
+            router_data
+                .get_merged_metadata()
+                .map(ExposeInterface::expose),
+            Some(serde_json::json!({
+                "merchant_reference": "request_reference",
+                "nested": { "request_only": 1, "connector_only": 2, "shared": "request" }
+            }))
+        );
+    }
+
+    #[test]
+    fn test_get_merged_metadata_with_disjoint_keys() {
+        let request = types::PaymentsAuthorizeData {
+            metadata: Some(Secret::new(serde_json::json!({ "order_id": "order_1" }))),
+            ..get_authorize_data()
+        };
+        let mut router_data = get_router_data(request, types::PaymentAddress::default());
+        router_data.connector_meta_data = Some(Secret::new(
+            serde_json::json!({ "terminal_id": "terminal_1" }),
+        ));
+
+        assert_eq!(
+            router_data
+                .get_merged_metadata()
+                .map(ExposeInterface::expose),
+            Some(serde_json::json!({ "order_id": "order_1", "terminal_id": "terminal_1" }))
+        );
+
+        router_data.connector_meta_data = Some(Secret::new(serde_json::json!("not_an_object")));
+        assert_eq!(
+            router_data
+                .get_merged_metadata()
+                .map(ExposeInterface::expose),
+            Some(serde_json::json!({ "order_id": "order_1" }))
+        );
+    }
+}
+
+#[cfg(test)]
+mod connector_meta_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct TestConnectorMeta {
+        #[allow(dead_code)]
+        merchant_account_id: String,
+    }
+
+    #[test]
+    fn test_to_connector_meta_with_context() {
+        let connector_meta = Some(serde_json::json!({ "merchant_account_id": "acc_1" }));
+
+        let result = to_connector_meta_with_context::<TestConnectorMeta>(
+            connector_meta,
+            "merchant_account_id",
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_to_connector_meta_with_context_includes_context_on_error() {
+        let connector_meta = Some(serde_json::json!({ "merchant_account_id": 1 }));
+
+        let error = to_connector_meta_with_context::<TestConnectorMeta>(
+            connector_meta,
+            "merchant_account_id",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::ParsingFailed
+        );
+        assert!(format!("{error:?}")
+            .contains("Failed to parse connector metadata: merchant_account_id"));
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct TestConnectorState {
+        transaction_id: String,
+        retry_count: u8,
+    }
+
+    #[test]
+    fn test_encode_connector_meta_round_trip() {
+        let connector_state = TestConnectorState {
+            transaction_id: "txn_1".to_string(),
+            retry_count: 2,
+        };
+
+        let connector_meta = encode_connector_meta(&connector_state).unwrap();
+        let decoded_state =
+            to_connector_meta_from_secret::<TestConnectorState>(Some(connector_meta)).unwrap();
+
+        assert_eq!(decoded_state, connector_state);
+    }
+
+    #[test]
+    fn test_to_connector_meta_from_secret_with_context_includes_context_on_error() {
+        let error = to_connector_meta_from_secret_with_context::<TestConnectorMeta>(
+            None,
+            "merchant_account_id",
+        )
+        .unwrap_err();
+
+        assert!(format!("{error:?}")
+            .contains("Failed to parse connector metadata: merchant_account_id"));
+    }
+
+    #[test]
+    fn test_decode_base64_meta_standard_alphabet() {
+        assert_eq!(decode_base64_meta("+/8=").unwrap(), vec![0xfb, 0xff]);
+    }
+
+    #[test]
+    fn test_decode_base64_meta_url_safe_alphabet() {
+        assert_eq!(decode_base64_meta("-_8=").unwrap(), vec![0xfb, 0xff]);
+    }
+
+    #[test]
+    fn test_decode_base64_meta_malformed() {
+        let error = decode_base64_meta("not base64!").unwrap_err();
+
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::ResponseDeserializationFailed
+        );
+    }
+}
+
+#[cfg(test)]
+mod card_data_tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn get_card(card_number: &str) -> domain::Card {
+        domain::Card {
+            card_number: cards::CardNumber::from_str(card_number).unwrap(),
+            card_exp_month: Secret::new("10".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_cvc: Secret::new("123".to_string()),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        }
+    }
+
+    #[test]
+    fn test_is_known_test_card() {
+        assert!(get_card("4111111111111111").is_known_test_card());
+        assert!(get_card("378282246310005").is_known_test_card());
+    }
+
+    #[test]
+    fn test_get_expiry_month_2_digit() {
+        for (month, expected_month) in [("3", "03"), ("03", "03"), ("12", "12")] {
+            let card = domain::Card {
+                card_exp_month: Secret::new(month.to_string()),
+                ..get_card("4111111111111111")
+            };
+            assert_eq!(
+                card.get_expiry_month_2_digit().unwrap().expose(),
+                expected_month
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_expiry_month_2_digit_with_invalid_month() {
+        let card = domain::Card {
+            card_exp_month: Secret::new("13".to_string()),
+            ..get_card("4111111111111111")
+        };
+
+        assert_eq!(
+            card.get_expiry_month_2_digit()
+                .unwrap_err()
+                .current_context(),
+            &errors::ConnectorError::InvalidDataFormat {
+                field_name: "card_exp_month"
+            }
+        );
+    }
+
+    #[test]
+    fn test_is_known_test_card_with_random_card() {
+        assert!(!get_card("4532015112830366").is_known_test_card());
+    }
+
+    #[test]
+    fn test_derive_payment_method_type_for_card() {
+        let credit_card = domain::Card {
+            card_type: Some("CREDIT".to_string()),
+            ..get_card("4111111111111111")
+        };
+
+        assert_eq!(
+            derive_payment_method_type(&domain::PaymentMethodData::Card(credit_card)),
+            Some(enums::PaymentMethodType::Credit)
+        );
+        assert_eq!(
+            derive_payment_method_type(&domain::PaymentMethodData::Card(get_card(
+                "4111111111111111"
+            ))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_derive_payment_method_type_for_wallet_and_bank() {
+        assert_eq!(
+            derive_payment_method_type(&domain::PaymentMethodData::Wallet(
+                domain::WalletData::DanaRedirect {}
+            )),
+            Some(enums::PaymentMethodType::Dana)
+        );
+        assert_eq!(
+            derive_payment_method_type(&domain::PaymentMethodData::Wallet(
+                domain::WalletData::TwintRedirect {}
+            )),
+            Some(enums::PaymentMethodType::Twint)
+        );
+        assert_eq!(
+            derive_payment_method_type(&domain::PaymentMethodData::BankRedirect(
+                domain::BankRedirectData::Blik { blik_code: None }
+            )),
+            Some(enums::PaymentMethodType::Blik)
+        );
+        assert_eq!(
+            derive_payment_method_type(&domain::PaymentMethodData::BankTransfer(Box::new(
+                domain::BankTransferData::Pix {}
+            ))),
+            Some(enums::PaymentMethodType::Pix)
+        );
+        assert_eq!(
+            derive_payment_method_type(&domain::PaymentMethodData::MandatePayment),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_all_card_issuers_with_overlapping_ranges() {
+        let card_issuers = get_all_card_issuers("676221260000000000");
+        assert_eq!(card_issuers.len(), 2);
+        assert!(card_issuers.contains(&CardIssuer::Discover));
+        assert!(card_issuers.contains(&CardIssuer::Maestro));
+    }
+
+    #[test]
+    fn test_get_all_card_issuers_with_single_match() {
+        let card_issuers = get_all_card_issuers("4111111111111111");
+        assert_eq!(card_issuers, vec![CardIssuer::Visa]);
+    }
+
+    #[test]
+    fn test_get_all_card_issuers_without_match() {
+        assert!(get_all_card_issuers("1234").is_empty());
+    }
+
+    #[test]
+    fn test_compute_card_fingerprint() {
+        let salt = b"merchant_salt";
+        let card = get_card("4111111111111111");
+        let same_card_with_spaces = domain::Card {
+            card_exp_month: Secret::new(" 10".to_string()),
+            card_exp_year: Secret::new("20 30".to_string()),
+            ..get_card("4111 1111 1111 1111")
+        };
+        let other_card = get_card("5555555555554444");
+
+        let fingerprint = compute_card_fingerprint(&card, salt);
+
+        assert_eq!(fingerprint.peek().len(), 64);
+        assert_eq!(
+            fingerprint.peek(),
+            compute_card_fingerprint(&same_card_with_spaces, salt).peek()
+        );
+        assert_ne!(
+            fingerprint.peek(),
+            compute_card_fingerprint(&other_card, salt).peek()
+        );
+        assert_ne!(
+            fingerprint.peek(),
+            compute_card_fingerprint(&card, b"other_merchant_salt").peek()
+        );
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = time::macros::datetime!(2024-03-15 10:00);
+        let card_with_expiry = |month: &str, year: &str| domain::Card {
+            card_exp_month: Secret::new(month.to_string()),
+            card_exp_year: Secret::new(year.to_string()),
+            ..get_card("4111111111111111")
+        };
+
+        assert!(card_with_expiry("02", "2024").is_expired(now).unwrap());
+        assert!(card_with_expiry("12", "23").is_expired(now).unwrap());
+        assert!(!card_with_expiry("03", "2024").is_expired(now).unwrap());
+        assert!(!card_with_expiry("3", "24").is_expired(now).unwrap());
+        assert!(!card_with_expiry("01", "2025").is_expired(now).unwrap());
+        assert!(card_with_expiry("13", "2024").is_expired(now).is_err());
+    }
+}
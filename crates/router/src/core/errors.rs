@@ -218,6 +218,12 @@ pub enum ConnectorError {
     },
     #[error("Invalid Configuration")]
     InvalidConnectorConfig { config: &'static str },
+    #[error("{field_name} exceeds the maximum length of {max_length} characters")]
+    MaxFieldLengthViolated {
+        field_name: &'static str,
+        max_length: usize,
+        received_length: usize,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -336,6 +342,68 @@ impl ConnectorError {
     pub fn is_connector_timeout(&self) -> bool {
         self == &Self::RequestTimeoutReceived
     }
+
+    /// Classifies whether retrying the same request could succeed.
+    ///
+    /// Only transient failures while talking to the connector (timeouts and unexpected
+    /// responses or processing failures) are considered retryable. Errors caused by the
+    /// request, the merchant configuration or unsupported functionality would fail again
+    /// in the same way and are treated as terminal.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RequestTimeoutReceived
+            | Self::ProcessingStepFailed(_)
+            | Self::UnexpectedResponseError(_) => true,
+            Self::FailedToObtainIntegrationUrl
+            | Self::RequestEncodingFailed
+            | Self::RequestEncodingFailedWithReason(_)
+            | Self::ParsingFailed
+            | Self::ResponseDeserializationFailed
+            | Self::RoutingRulesParsingError
+            | Self::FailedToObtainPreferredConnector
+            | Self::InvalidConnectorName
+            | Self::InvalidWallet
+            | Self::ResponseHandlingFailed
+            | Self::MissingRequiredField { .. }
+            | Self::MissingRequiredFields { .. }
+            | Self::FailedToObtainAuthType
+            | Self::FailedToObtainCertificate
+            | Self::NoConnectorMetaData
+            | Self::FailedToObtainCertificateKey
+            | Self::NotImplemented(_)
+            | Self::NotSupported { .. }
+            | Self::FlowNotSupported { .. }
+            | Self::CaptureMethodNotSupported
+            | Self::MissingConnectorMandateID
+            | Self::MissingConnectorTransactionID
+            | Self::MissingConnectorRefundID
+            | Self::MissingApplePayTokenData
+            | Self::WebhooksNotImplemented
+            | Self::WebhookBodyDecodingFailed
+            | Self::WebhookSignatureNotFound
+            | Self::WebhookSourceVerificationFailed
+            | Self::WebhookVerificationSecretNotFound
+            | Self::WebhookVerificationSecretInvalid
+            | Self::WebhookReferenceIdNotFound
+            | Self::WebhookEventTypeNotFound
+            | Self::WebhookResourceObjectNotFound
+            | Self::WebhookResponseEncodingFailed
+            | Self::InvalidDateFormat
+            | Self::DateFormattingFailed
+            | Self::InvalidDataFormat { .. }
+            | Self::MismatchedPaymentData
+            | Self::InvalidWalletToken { .. }
+            | Self::MissingConnectorRelatedTransactionID { .. }
+            | Self::FileValidationFailed { .. }
+            | Self::MissingConnectorRedirectionPayload { .. }
+            | Self::FailedAtConnector { .. }
+            | Self::MissingPaymentMethodType
+            | Self::InSufficientBalanceInPaymentMethod
+            | Self::MaxFieldLengthViolated { .. }
+            | Self::CurrencyNotSupported { .. }
+            | Self::InvalidConnectorConfig { .. } => false,
+        }
+    }
 }
 
 #[cfg(feature = "detailed_errors")]
@@ -443,3 +511,38 @@ pub enum ConditionalConfigError {
     #[error("Error constructing the Input")]
     InputConstructionError,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connector_error_is_retryable() {
+        assert!(ConnectorError::RequestTimeoutReceived.is_retryable());
+        assert!(ConnectorError::ProcessingStepFailed(None).is_retryable());
+        assert!(
+            ConnectorError::UnexpectedResponseError(bytes::Bytes::from_static(b"Bad Gateway"))
+                .is_retryable()
+        );
+    }
+
+    #[test]
+    fn test_connector_error_is_terminal() {
+        assert!(!ConnectorError::NotImplemented("Payouts".to_string()).is_retryable());
+        assert!(!ConnectorError::MissingRequiredField {
+            field_name: "billing.address"
+        }
+        .is_retryable());
+        assert!(!ConnectorError::FailedToObtainAuthType.is_retryable());
+        assert!(!ConnectorError::CurrencyNotSupported {
+            message: "USD".to_string(),
+            connector: "dummy",
+        }
+        .is_retryable());
+        assert!(!ConnectorError::FailedAtConnector {
+            message: "Card declined".to_string(),
+            code: "05".to_string(),
+        }
+        .is_retryable());
+    }
+}
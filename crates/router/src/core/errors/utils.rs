@@ -215,6 +215,7 @@ impl<T> ConnectorErrorExt<T> for error_stack::Result<T, errors::ConnectorError>
             | errors::ConnectorError::FailedAtConnector { .. }
             | errors::ConnectorError::MissingPaymentMethodType
             | errors::ConnectorError::InSufficientBalanceInPaymentMethod
+            | errors::ConnectorError::MaxFieldLengthViolated { .. }
             | errors::ConnectorError::RequestTimeoutReceived
             | errors::ConnectorError::CurrencyNotSupported { .. }
             | errors::ConnectorError::InvalidConnectorConfig { .. } => {
@@ -269,6 +270,9 @@ impl<T> ConnectorErrorExt<T> for error_stack::Result<T, errors::ConnectorError>
                 errors::ConnectorError::InvalidDataFormat { field_name } => {
                     errors::ApiErrorResponse::InvalidDataValue { field_name }
                 },
+                errors::ConnectorError::MaxFieldLengthViolated { field_name, .. } => {
+                    errors::ApiErrorResponse::InvalidDataValue { field_name }
+                },
                 errors::ConnectorError::InvalidWalletToken {wallet_name} => errors::ApiErrorResponse::InvalidWalletToken {wallet_name: wallet_name.to_string()},
                 errors::ConnectorError::CurrencyNotSupported { message, connector} => errors::ApiErrorResponse::CurrencyNotSupported { message: format!("Credentials for the currency {message} are not configured with the connector {connector}/hyperswitch") },
                 errors::ConnectorError::FailedToObtainAuthType =>  errors::ApiErrorResponse::InvalidConnectorConfiguration {config: "connector_account_details".to_string()},
@@ -399,6 +403,7 @@ impl<T> ConnectorErrorExt<T> for error_stack::Result<T, errors::ConnectorError>
                 | errors::ConnectorError::FailedAtConnector { .. }
                 | errors::ConnectorError::MissingPaymentMethodType
                 | errors::ConnectorError::InSufficientBalanceInPaymentMethod
+                | errors::ConnectorError::MaxFieldLengthViolated { .. }
                 | errors::ConnectorError::RequestTimeoutReceived
                 | errors::ConnectorError::CurrencyNotSupported { .. }
                 | errors::ConnectorError::ProcessingStepFailed(None) => {
@@ -9,6 +9,7 @@ use router_env::{
 };
 
 use crate::{
+    connector,
     core::{
         errors::{self, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
@@ -57,6 +58,8 @@ where
     Ctx: PaymentMethodRetrieve,
 {
     let mut retries = None;
+    let mut retry_backoff = None;
+    let mut retry_budget = None;
 
     metrics::AUTO_RETRY_ELIGIBLE_REQUEST_COUNT.add(&metrics::CONTEXT, 1, &[]);
 
@@ -114,14 +117,25 @@ where
                     retries = get_retries(state, retries, &merchant_account.merchant_id).await;
 
                     if retries.is_none() || retries == Some(0) {
-                        metrics::AUTO_RETRY_EXHAUSTED_COUNT.add(&metrics::CONTEXT, 1, &[]);
-                        logger::info!("retries exhausted for auto_retry payment");
+                        AutoRetryStopReason::RetriesExhausted.record();
                         break;
                     }
 
                     if connectors.len() == 0 {
-                        logger::info!("connectors exhausted for auto_retry payment");
-                        metrics::AUTO_RETRY_EXHAUSTED_COUNT.add(&metrics::CONTEXT, 1, &[]);
+                        AutoRetryStopReason::ConnectorsExhausted.record();
+                        break;
+                    }
+
+                    if let Err(reason) = prepare_auto_retry(
+                        state,
+                        &merchant_account.merchant_id,
+                        payment_data.payment_intent.attempt_count,
+                        &mut retry_budget,
+                        &mut retry_backoff,
+                    )
+                    .await
+                    {
+                        reason.record();
                         break;
                     }
 
@@ -162,6 +176,60 @@ where
     Ok(router_data)
 }
 
+/// Reason for which the auto retries of a payment are stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoRetryStopReason {
+    RetriesExhausted,
+    ConnectorsExhausted,
+    BudgetExhausted,
+}
+
+impl AutoRetryStopReason {
+    /// Counter incremented when the auto retries of a payment are stopped for this reason
+    fn metric(self) -> &'static router_env::opentelemetry::metrics::Counter<u64> {
+        match self {
+            Self::RetriesExhausted | Self::ConnectorsExhausted => {
+                &*metrics::AUTO_RETRY_EXHAUSTED_COUNT
+            }
+            Self::BudgetExhausted => &*metrics::AUTO_RETRY_BUDGET_EXHAUSTED_COUNT,
+        }
+    }
+
+    fn record(self) {
+        logger::info!(reason = ?self, "stopping auto retries for auto_retry payment");
+        self.metric().add(&metrics::CONTEXT, 1, &[]);
+    }
+}
+
+/// Checks whether the next auto retry of the payment can be made, consuming a token from the retry
+/// budget of the merchant, and waits for the backoff delay of the attempt following the
+/// `attempt_count` attempts already made. The configs are loaded once and cached in
+/// `retry_budget` and `retry_backoff`.
+async fn prepare_auto_retry(
+    state: &app::AppState,
+    merchant_id: &str,
+    attempt_count: i16,
+    retry_budget: &mut Option<RetryBudgetConfig>,
+    retry_backoff: &mut Option<RetryBackoffConfig>,
+) -> Result<(), AutoRetryStopReason> {
+    let budget = match *retry_budget {
+        Some(budget) => budget,
+        None => get_retry_budget_config(state, merchant_id).await,
+    };
+    *retry_budget = Some(budget);
+    if !consume_retry_budget(state, merchant_id, &budget).await {
+        return Err(AutoRetryStopReason::BudgetExhausted);
+    }
+
+    let backoff = match *retry_backoff {
+        Some(backoff) => backoff,
+        None => get_retry_backoff_config(state, merchant_id).await,
+    };
+    *retry_backoff = Some(backoff);
+    wait_before_retry(&backoff, attempt_count.saturating_add(1)).await;
+    Ok(())
+}
+
 #[instrument(skip_all)]
 pub async fn is_step_up_enabled_for_merchant_connector(
     state: &app::AppState,
@@ -216,6 +284,158 @@ pub async fn get_retries(
     }
 }
 
+/// Number of auto retries a payment of the merchant has left after `attempts_made` retries,
+/// `None` if auto retries are not configured for the merchant
+#[instrument(skip_all)]
+pub async fn remaining_retries(
+    state: &app::AppState,
+    merchant_id: &str,
+    attempts_made: i32,
+) -> Option<i32> {
+    get_retries(state, None, merchant_id)
+        .await
+        .map(|max_retries| max_retries.saturating_sub(attempts_made).max(0))
+}
+
+/// Upper bound for the delay introduced between two auto retries, irrespective of the merchant config
+const MAX_RETRY_BACKOFF_IN_MILLIS: u64 = 5000;
+
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct RetryBackoffConfig {
+    /// Base delay in milliseconds, a value of zero disables the backoff
+    pub base_delay_in_millis: u64,
+    /// Maximum delay in milliseconds between two attempts
+    pub max_delay_in_millis: u64,
+}
+
+impl RetryBackoffConfig {
+    /// Computes the jittered delay to wait before making the attempt with the given attempt number.
+    /// The first attempt is never delayed.
+    pub fn get_delay(&self, attempt_number: i16) -> Option<std::time::Duration> {
+        if self.base_delay_in_millis == 0 || attempt_number <= 1 {
+            return None;
+        }
+
+        let cap = self
+            .max_delay_in_millis
+            .max(self.base_delay_in_millis)
+            .min(MAX_RETRY_BACKOFF_IN_MILLIS);
+        // The delay doubles for every subsequent retry, starting from the base delay for the second attempt
+        let exponent = u32::try_from(attempt_number - 2).unwrap_or(u32::MAX);
+        let delay = 2_u64
+            .checked_pow(exponent)
+            .and_then(|multiplier| self.base_delay_in_millis.checked_mul(multiplier))
+            .unwrap_or(cap)
+            .min(cap);
+
+        // Equal jitter: wait for at least half the delay and a random portion of the other half
+        let half_delay = delay / 2;
+        let jitter = rand::Rng::gen_range(&mut rand::thread_rng(), 0..=delay - half_delay);
+
+        Some(std::time::Duration::from_millis(half_delay + jitter))
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn get_retry_backoff_config(
+    state: &app::AppState,
+    merchant_id: &str,
+) -> RetryBackoffConfig {
+    get_merchant_retry_config(state, &format!("auto_retry_backoff_{merchant_id}")).await
+}
+
+pub async fn wait_before_retry(backoff: &RetryBackoffConfig, attempt_number: i16) {
+    if let Some(delay) = backoff.get_delay(attempt_number) {
+        logger::info!(?delay, "waiting before auto retry");
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct RetryBudgetConfig {
+    /// Maximum number of auto retries across all payments of the merchant within the window,
+    /// a value of zero disables the budget
+    pub max_retries: u64,
+    /// Length of the sliding window in seconds
+    pub window_in_secs: u64,
+}
+
+impl RetryBudgetConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_retries > 0 && self.window_in_secs > 0
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn get_retry_budget_config(
+    state: &app::AppState,
+    merchant_id: &str,
+) -> RetryBudgetConfig {
+    get_merchant_retry_config(state, &format!("auto_retry_budget_{merchant_id}")).await
+}
+
+/// Reads the retry config stored under `key`, falling back to the default config if it is
+/// missing or cannot be parsed
+async fn get_merchant_retry_config<T>(state: &app::AppState, key: &str) -> T
+where
+    T: serde::de::DeserializeOwned + Default,
+{
+    let db = &*state.store;
+    db.find_config_by_key_unwrap_or(key, Some("{}".to_string()))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .and_then(|retry_config| {
+            serde_json::from_str::<T>(&retry_config.config)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| format!("Retry config parsing failed for {key}"))
+        })
+        .map_err(|err| {
+            logger::error!(retry_config_error=?err);
+        })
+        .unwrap_or_default()
+}
+
+/// Consumes a token from the retry budget of the merchant, returns `false` if the budget is exhausted.
+/// Retries are allowed if the budget could not be checked.
+#[instrument(skip_all)]
+pub async fn consume_retry_budget(
+    state: &app::AppState,
+    merchant_id: &str,
+    budget: &RetryBudgetConfig,
+) -> bool {
+    if !budget.is_enabled() {
+        return true;
+    }
+
+    match state.store.get_redis_conn() {
+        Ok(redis_conn) => consume_retry_budget_token(&redis_conn, merchant_id, budget).await,
+        Err(err) => {
+            logger::error!(retry_budget_error=?err);
+            true
+        }
+    }
+}
+
+async fn consume_retry_budget_token(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    merchant_id: &str,
+    budget: &RetryBudgetConfig,
+) -> bool {
+    redis_conn
+        .consume_sliding_window_token(
+            &format!("auto_retry_budget_{merchant_id}"),
+            budget.max_retries,
+            std::time::Duration::from_secs(budget.window_in_secs),
+        )
+        .await
+        .map_err(|err| {
+            logger::error!(retry_budget_error=?err);
+        })
+        .unwrap_or(true)
+}
+
 #[instrument(skip_all)]
 pub async fn get_gsm<F, FData>(
     state: &app::AppState,
@@ -237,6 +457,7 @@ pub fn get_gsm_decision(
     option_gsm: Option<storage::gsm::GatewayStatusMap>,
 ) -> api_models::gsm::GsmDecision {
     let option_gsm_decision = option_gsm
+            .as_ref()
             .and_then(|gsm| {
                 api_models::gsm::GsmDecision::from_str(gsm.decision.as_str())
                     .map_err(|err| {
@@ -248,12 +469,28 @@ pub fn get_gsm_decision(
                     .ok()
             });
 
-    if option_gsm_decision.is_some() {
-        metrics::AUTO_RETRY_GSM_MATCH_COUNT.add(&metrics::CONTEXT, 1, &[]);
+    if let Some(gsm) = option_gsm
+        .as_ref()
+        .filter(|_| option_gsm_decision.is_some())
+    {
+        metrics::AUTO_RETRY_GSM_MATCH_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &get_gsm_decision_metric_attributes(gsm),
+        );
     }
     option_gsm_decision.unwrap_or_default()
 }
 
+fn get_gsm_decision_metric_attributes(
+    gsm: &storage::gsm::GatewayStatusMap,
+) -> [router_env::opentelemetry::KeyValue; 2] {
+    [
+        metrics::request::add_attributes("connector", gsm.connector.clone()),
+        metrics::request::add_attributes("flow", gsm.flow.clone()),
+    ]
+}
+
 #[inline]
 fn get_flow_name<F>() -> RouterResult<String> {
     Ok(std::any::type_name::<F>()
@@ -341,7 +578,7 @@ where
         payment_data.payment_attempt.clone(),
         new_attempt_count,
         is_step_up,
-    );
+    )?;
 
     let db = &*state.store;
     let additional_payment_method_data =
@@ -394,11 +631,11 @@ where
                     error_code: None,
                     error_message: None,
                     error_reason: None,
-                    amount_capturable: if router_data.status.is_terminal_status() {
-                        Some(0)
-                    } else {
-                        None
-                    },
+                    amount_capturable: Some(get_remaining_amount_capturable(
+                        payment_data.payment_attempt.get_total_amount(),
+                        payment_data.payment_intent.amount_captured,
+                        router_data.status,
+                    )),
                     updated_by: storage_scheme.to_string(),
                     authentication_data,
                     encoded_data,
@@ -417,6 +654,15 @@ where
         }
         Err(ref error_response) => {
             let option_gsm = get_gsm(state, &router_data).await?;
+            let decline_reason =
+                connector::utils::classify_decline(&error_response.code, &error_response.message);
+            logger::info!(?decline_reason, "classified connector decline");
+            // The normalized decline reason stands in for the unified code if GSM has none
+            let unified_code = option_gsm
+                .as_ref()
+                .map(|gsm| gsm.unified_code.clone())
+                .filter(Option::is_some)
+                .or_else(|| decline_reason.as_code().map(|code| Some(code.to_string())));
 
             db.update_payment_attempt_with_attempt_id(
                 payment_data.payment_attempt.clone(),
@@ -428,7 +674,7 @@ where
                     error_reason: Some(error_response.reason.clone()),
                     amount_capturable: Some(0),
                     updated_by: storage_scheme.to_string(),
-                    unified_code: option_gsm.clone().map(|gsm| gsm.unified_code),
+                    unified_code,
                     unified_message: option_gsm.map(|gsm| gsm.unified_message),
                     connector_transaction_id: error_response.connector_transaction_id.clone(),
                     payment_method_data: additional_payment_method_data,
@@ -466,15 +712,37 @@ where
     Ok(())
 }
 
+/// Computes the amount that is still capturable on the attempt, so that a retry after a
+/// partial capture does not persist a stale capturable amount. A captured amount exceeding
+/// the total leaves nothing capturable instead of failing the tracker update.
+pub fn get_remaining_amount_capturable(
+    total_amount: i64,
+    amount_captured: Option<i64>,
+    attempt_status: storage_enums::AttemptStatus,
+) -> i64 {
+    if attempt_status.is_terminal_status() {
+        return 0;
+    }
+    match total_amount.checked_sub(amount_captured.unwrap_or(0)) {
+        Some(remaining_amount) if remaining_amount >= 0 => remaining_amount,
+        _ => {
+            logger::warn!(
+                "Captured amount {amount_captured:?} exceeds the total amount {total_amount}, marking nothing as capturable"
+            );
+            0
+        }
+    }
+}
+
 #[instrument(skip_all)]
 pub fn make_new_payment_attempt(
     connector: String,
     old_payment_attempt: storage::PaymentAttempt,
     new_attempt_count: i16,
     is_step_up: bool,
-) -> storage::PaymentAttemptNew {
+) -> RouterResult<storage::PaymentAttemptNew> {
     let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
-    storage::PaymentAttemptNew {
+    let mut payment_attempt_new = storage::PaymentAttemptNew {
         connector: Some(connector),
         attempt_id: utils::get_payment_attempt_id(
             &old_payment_attempt.payment_id,
@@ -511,7 +779,12 @@ pub fn make_new_payment_attempt(
         modified_at,
         last_synced,
         ..storage::PaymentAttemptNew::default()
-    }
+    };
+    payment_attempt_new
+        .compute_net_amount()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to compute the net amount of the new payment attempt")?;
+    Ok(payment_attempt_new)
 }
 
 pub async fn config_should_call_gsm(db: &dyn StorageInterface, merchant_id: &String) -> bool {
@@ -574,3 +847,747 @@ impl<F: Send + Clone + Sync, FData: Send + Sync>
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_backoff_is_not_applied_before_first_attempt() {
+        let backoff = RetryBackoffConfig {
+            base_delay_in_millis: 100,
+            max_delay_in_millis: 1000,
+        };
+        let start = tokio::time::Instant::now();
+
+        wait_before_retry(&backoff, 1).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        wait_before_retry(&backoff, 2).await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed <= Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_backoff_is_capped() {
+        let backoff = RetryBackoffConfig {
+            base_delay_in_millis: 100,
+            max_delay_in_millis: 300,
+        };
+        let start = tokio::time::Instant::now();
+
+        wait_before_retry(&backoff, 10).await;
+        assert!(start.elapsed() <= Duration::from_millis(300));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_backoff_is_skipped_for_zero_config() {
+        let backoff = RetryBackoffConfig::default();
+        let start = tokio::time::Instant::now();
+
+        wait_before_retry(&backoff, 2).await;
+        wait_before_retry(&backoff, 3).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_gsm_decision_metric_attributes() {
+        let now = common_utils::date_time::now();
+        let gsm = storage::gsm::GatewayStatusMap {
+            connector: "stripe".to_string(),
+            flow: "Authorize".to_string(),
+            sub_flow: "sub_flow".to_string(),
+            code: "card_declined".to_string(),
+            message: "Your card was declined".to_string(),
+            status: "Failure".to_string(),
+            router_error: None,
+            decision: "retry".to_string(),
+            created_at: now,
+            last_modified: now,
+            step_up_possible: false,
+            unified_code: None,
+            unified_message: None,
+        };
+
+        assert_eq!(
+            get_gsm_decision(Some(gsm.clone())),
+            api_models::gsm::GsmDecision::Retry
+        );
+        assert_eq!(
+            get_gsm_decision_metric_attributes(&gsm),
+            [
+                router_env::opentelemetry::KeyValue::new("connector", "stripe"),
+                router_env::opentelemetry::KeyValue::new("flow", "Authorize"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remaining_amount_capturable_after_partial_capture_retry() {
+        // First attempt captured part of the payment, the retried attempt is still pending
+        let remaining_amount =
+            get_remaining_amount_capturable(1000, Some(300), storage_enums::AttemptStatus::Pending);
+        assert_eq!(remaining_amount, 700);
+
+        let remaining_amount =
+            get_remaining_amount_capturable(1000, Some(300), storage_enums::AttemptStatus::Charged);
+        assert_eq!(remaining_amount, 0);
+    }
+
+    #[test]
+    fn test_remaining_amount_capturable_is_clamped_on_over_capture() {
+        let remaining_amount = get_remaining_amount_capturable(
+            1000,
+            Some(1200),
+            storage_enums::AttemptStatus::Pending,
+        );
+        assert_eq!(remaining_amount, 0);
+    }
+
+    #[test]
+    fn test_compute_net_amount_without_surcharge() {
+        let mut payment_attempt_new = storage::PaymentAttemptNew {
+            amount: 1000,
+            ..storage::PaymentAttemptNew::default()
+        };
+
+        assert!(payment_attempt_new.compute_net_amount().is_ok());
+        assert_eq!(payment_attempt_new.net_amount, 1000);
+    }
+
+    #[test]
+    fn test_compute_net_amount_with_surcharge() {
+        let mut payment_attempt_new = storage::PaymentAttemptNew {
+            amount: 1000,
+            surcharge_amount: Some(50),
+            tax_amount: Some(10),
+            offer_amount: Some(100),
+            ..storage::PaymentAttemptNew::default()
+        };
+
+        assert!(payment_attempt_new.compute_net_amount().is_ok());
+        assert_eq!(payment_attempt_new.net_amount, 1060);
+        assert_eq!(
+            payment_attempt_new
+                .populate_derived_fields()
+                .map(|payment_attempt_new| payment_attempt_new.net_amount)
+                .ok(),
+            Some(1060)
+        );
+    }
+
+    #[test]
+    fn test_compute_net_amount_reports_overflow() {
+        let mut payment_attempt_new = storage::PaymentAttemptNew {
+            amount: i64::MAX,
+            surcharge_amount: Some(1),
+            net_amount: 10,
+            ..storage::PaymentAttemptNew::default()
+        };
+
+        assert!(payment_attempt_new.compute_net_amount().is_err());
+        assert_eq!(payment_attempt_new.net_amount, 10);
+
+        let overflowing_payment_attempt = storage::PaymentAttemptNew {
+            amount: i64::MAX,
+            surcharge_amount: Some(1),
+            ..storage::PaymentAttemptNew::default()
+        };
+        assert!(overflowing_payment_attempt
+            .populate_derived_fields()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_is_exhausted() {
+        #[allow(clippy::expect_used)]
+        let redis_conn =
+            redis_interface::RedisConnectionPool::new(&redis_interface::RedisSettings::default())
+                .await
+                .expect("failed to create redis connection pool");
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+        let budget = RetryBudgetConfig {
+            max_retries: 2,
+            window_in_secs: 60,
+        };
+
+        let mut retries = 0;
+        while retries < 5 && consume_retry_budget_token(&redis_conn, &merchant_id, &budget).await {
+            retries += 1;
+        }
+
+        assert_eq!(retries, 2);
+    }
+
+    #[test]
+    fn test_retry_budget_is_disabled_by_default() {
+        assert!(!RetryBudgetConfig::default().is_enabled());
+    }
+
+    async fn get_mock_state() -> app::AppState {
+        #[allow(clippy::expect_used)]
+        let conf = crate::configs::settings::Settings::new().expect("invalid settings");
+        let tx = tokio::sync::oneshot::channel().0;
+        Box::pin(app::AppState::with_storage(
+            conf,
+            crate::db::StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        ))
+        .await
+    }
+
+    fn get_response_update(
+        status: storage_enums::AttemptStatus,
+        amount_capturable: i64,
+    ) -> storage::PaymentAttemptUpdate {
+        storage::PaymentAttemptUpdate::ResponseUpdate {
+            status,
+            connector: None,
+            connector_transaction_id: None,
+            authentication_type: None,
+            payment_method_id: None,
+            mandate_id: None,
+            connector_metadata: None,
+            payment_token: None,
+            error_code: None,
+            error_message: None,
+            error_reason: None,
+            connector_response_reference_id: None,
+            amount_capturable: Some(amount_capturable),
+            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            authentication_data: None,
+            encoded_data: None,
+            unified_code: None,
+            unified_message: None,
+            payment_method_data: None,
+        }
+    }
+
+    #[tokio::test]
+    #[allow(clippy::expect_used)]
+    async fn test_partial_capture_followed_by_retry() {
+        let state = get_mock_state().await;
+        let db = &*state.store;
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+        let payment_id = common_utils::generate_id_with_default_len("pay");
+
+        // Arrange
+        let payment_attempt = db
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: payment_id.clone(),
+                    merchant_id: common_utils::generate_id_with_default_len("merchant"),
+                    attempt_id: utils::get_payment_attempt_id(&payment_id, 1),
+                    status: storage_enums::AttemptStatus::Pending,
+                    amount: 1000,
+                    amount_capturable: 1000,
+                    ..storage::PaymentAttemptNew::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .expect("failed to insert payment attempt");
+
+        // Act
+        let status = storage_enums::AttemptStatus::Pending;
+        let partially_captured_attempt = db
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt.clone(),
+                get_response_update(
+                    status,
+                    get_remaining_amount_capturable(
+                        payment_attempt.get_total_amount(),
+                        Some(300),
+                        status,
+                    ),
+                ),
+                storage_scheme,
+            )
+            .await
+            .expect("failed to update payment attempt");
+        let over_captured_attempt = db
+            .update_payment_attempt_with_attempt_id(
+                partially_captured_attempt.clone(),
+                get_response_update(
+                    status,
+                    get_remaining_amount_capturable(
+                        partially_captured_attempt.get_total_amount(),
+                        Some(1200),
+                        status,
+                    ),
+                ),
+                storage_scheme,
+            )
+            .await
+            .expect("failed to update payment attempt");
+        let retried_attempt = db
+            .insert_payment_attempt(
+                make_new_payment_attempt(
+                    "stripe".to_string(),
+                    partially_captured_attempt.clone(),
+                    2,
+                    false,
+                )
+                .expect("failed to make new payment attempt"),
+                storage_scheme,
+            )
+            .await
+            .expect("failed to insert retried payment attempt");
+
+        // Assert
+        assert_eq!(partially_captured_attempt.amount_capturable, 700);
+        assert_eq!(over_captured_attempt.amount_capturable, 0);
+        assert_eq!(
+            retried_attempt.attempt_id,
+            utils::get_payment_attempt_id(&payment_id, 2)
+        );
+        assert_eq!(retried_attempt.amount, partially_captured_attempt.amount);
+        assert_eq!(retried_attempt.net_amount, 1000);
+    }
+
+    fn get_payment_intent_new(
+        payment_id: &str,
+        merchant_id: &str,
+        attempt_id: &str,
+    ) -> storage::PaymentIntentNew {
+        storage::PaymentIntentNew {
+            payment_id: payment_id.to_string(),
+            merchant_id: merchant_id.to_string(),
+            status: storage_enums::IntentStatus::PartiallyCapturedAndCapturable,
+            amount: 1000,
+            currency: Some(storage_enums::Currency::USD),
+            amount_captured: Some(300),
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: None,
+            modified_at: None,
+            last_synced: None,
+            setup_future_usage: None,
+            off_session: None,
+            client_secret: None,
+            active_attempt: hyperswitch_domain_models::RemoteStorageObject::ForeignID(
+                attempt_id.to_string(),
+            ),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            profile_id: None,
+            merchant_decision: None,
+            payment_link_id: None,
+            payment_confirm_source: None,
+            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            surcharge_applicable: None,
+            request_incremental_authorization: None,
+            incremental_authorization_allowed: None,
+            authorization_count: None,
+            fingerprint_id: None,
+            session_expiry: None,
+            request_external_three_ds_authentication: None,
+        }
+    }
+
+    fn get_payment_data(
+        payment_intent: storage::PaymentIntent,
+        payment_attempt: storage::PaymentAttempt,
+    ) -> payments::PaymentData<api::Authorize> {
+        payments::PaymentData {
+            flow: std::marker::PhantomData,
+            payment_intent,
+            payment_attempt,
+            multiple_capture_data: None,
+            amount: api::Amount::from(1000),
+            mandate_id: None,
+            mandate_connector: None,
+            currency: storage_enums::Currency::USD,
+            setup_mandate: None,
+            customer_acceptance: None,
+            address: payments::PaymentAddress::default(),
+            token: None,
+            token_data: None,
+            confirm: None,
+            force_sync: None,
+            payment_method_data: None,
+            payment_method_info: None,
+            refunds: vec![],
+            disputes: vec![],
+            attempts: None,
+            sessions_token: vec![],
+            card_cvc: None,
+            email: None,
+            creds_identifier: None,
+            pm_token: None,
+            connector_customer_id: None,
+            recurring_mandate_payment_data: None,
+            ephemeral_key: None,
+            redirect_response: None,
+            surcharge_details: None,
+            frm_message: None,
+            payment_link_data: None,
+            incremental_authorization_details: None,
+            authorizations: vec![],
+            authentication: None,
+            frm_metadata: None,
+            recurring_details: None,
+            poll_config: None,
+        }
+    }
+
+    fn get_router_data(
+        payment_attempt: &storage::PaymentAttempt,
+        status: storage_enums::AttemptStatus,
+    ) -> types::RouterData<api::Authorize, (), types::PaymentsResponseData> {
+        types::RouterData {
+            flow: std::marker::PhantomData,
+            merchant_id: payment_attempt.merchant_id.clone(),
+            customer_id: None,
+            connector_customer: None,
+            connector: "stripe".to_string(),
+            payment_id: payment_attempt.payment_id.clone(),
+            attempt_id: payment_attempt.attempt_id.clone(),
+            status,
+            payment_method: storage_enums::PaymentMethod::Card,
+            connector_auth_type: types::ConnectorAuthType::default(),
+            description: None,
+            return_url: None,
+            address: payments::PaymentAddress::default(),
+            auth_type: storage_enums::AuthenticationType::NoThreeDs,
+            connector_meta_data: None,
+            amount_captured: None,
+            access_token: None,
+            session_token: None,
+            reference_id: None,
+            payment_method_token: None,
+            recurring_mandate_payment_data: None,
+            preprocessing_id: None,
+            payment_method_balance: None,
+            connector_api_version: None,
+            request: (),
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId("txn_1".to_string()),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+                incremental_authorization_allowed: None,
+            }),
+            connector_request_reference_id: payment_attempt.attempt_id.clone(),
+            #[cfg(feature = "payouts")]
+            payout_method_data: None,
+            #[cfg(feature = "payouts")]
+            quote_id: None,
+            test_mode: None,
+            connector_http_status_code: None,
+            external_latency: None,
+            apple_pay_flow: None,
+            frm_metadata: None,
+            dispute_id: None,
+            refund_id: None,
+            connector_response: None,
+            payment_method_status: None,
+        }
+    }
+
+    #[tokio::test]
+    #[allow(clippy::expect_used)]
+    async fn test_modify_trackers_after_partial_capture() {
+        let state = get_mock_state().await;
+        let db = &*state.store;
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+        let payment_id = common_utils::generate_id_with_default_len("pay");
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+        let attempt_id = utils::get_payment_attempt_id(&payment_id, 1);
+
+        // Arrange
+        let payment_attempt = db
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: payment_id.clone(),
+                    merchant_id: merchant_id.clone(),
+                    attempt_id: attempt_id.clone(),
+                    status: storage_enums::AttemptStatus::PartialChargedAndChargeable,
+                    amount: 1000,
+                    amount_capturable: 1000,
+                    ..storage::PaymentAttemptNew::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .expect("failed to insert payment attempt");
+        let payment_intent = db
+            .insert_payment_intent(
+                get_payment_intent_new(&payment_id, &merchant_id, &attempt_id),
+                storage_scheme,
+            )
+            .await
+            .expect("failed to insert payment intent");
+        let router_data = get_router_data(
+            &payment_attempt,
+            storage_enums::AttemptStatus::PartialChargedAndChargeable,
+        );
+        let mut payment_data = get_payment_data(payment_intent, payment_attempt);
+
+        // Act
+        modify_trackers(
+            &state,
+            "adyen".to_string(),
+            &mut payment_data,
+            storage_scheme,
+            router_data,
+            false,
+        )
+        .await
+        .expect("failed to modify trackers");
+
+        // Assert
+        let updated_attempt = db
+            .find_payment_attempt_by_attempt_id_merchant_id(
+                &attempt_id,
+                &merchant_id,
+                storage_scheme,
+            )
+            .await
+            .expect("failed to find payment attempt");
+        assert_eq!(updated_attempt.amount_capturable, 700);
+        assert_eq!(
+            updated_attempt.connector_transaction_id.as_deref(),
+            Some("txn_1")
+        );
+        assert_eq!(
+            payment_data.payment_attempt.attempt_id,
+            utils::get_payment_attempt_id(&payment_id, 2)
+        );
+        assert_eq!(payment_data.payment_intent.attempt_count, 2);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::expect_used)]
+    async fn test_modify_trackers_stores_normalized_decline_reason() {
+        let state = get_mock_state().await;
+        let db = &*state.store;
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+        let payment_id = common_utils::generate_id_with_default_len("pay");
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+        let attempt_id = utils::get_payment_attempt_id(&payment_id, 1);
+
+        // Arrange
+        let payment_attempt = db
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: payment_id.clone(),
+                    merchant_id: merchant_id.clone(),
+                    attempt_id: attempt_id.clone(),
+                    status: storage_enums::AttemptStatus::Pending,
+                    amount: 1000,
+                    amount_capturable: 1000,
+                    ..storage::PaymentAttemptNew::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .expect("failed to insert payment attempt");
+        let payment_intent = db
+            .insert_payment_intent(
+                get_payment_intent_new(&payment_id, &merchant_id, &attempt_id),
+                storage_scheme,
+            )
+            .await
+            .expect("failed to insert payment intent");
+        let router_data = types::RouterData {
+            response: Err(types::ErrorResponse {
+                code: "51".to_string(),
+                message: "Declined".to_string(),
+                reason: None,
+                status_code: 402,
+                attempt_status: None,
+                connector_transaction_id: None,
+            }),
+            ..get_router_data(&payment_attempt, storage_enums::AttemptStatus::Failure)
+        };
+        let mut payment_data = get_payment_data(payment_intent, payment_attempt);
+
+        // Act
+        modify_trackers(
+            &state,
+            "adyen".to_string(),
+            &mut payment_data,
+            storage_scheme,
+            router_data,
+            false,
+        )
+        .await
+        .expect("failed to modify trackers");
+
+        // Assert
+        let updated_attempt = db
+            .find_payment_attempt_by_attempt_id_merchant_id(
+                &attempt_id,
+                &merchant_id,
+                storage_scheme,
+            )
+            .await
+            .expect("failed to find payment attempt");
+        assert_eq!(
+            updated_attempt.status,
+            storage_enums::AttemptStatus::Failure
+        );
+        assert_eq!(
+            updated_attempt.unified_code.as_deref(),
+            Some("insufficient_funds")
+        );
+    }
+
+    #[tokio::test]
+    #[allow(clippy::expect_used)]
+    async fn test_get_merchant_retry_config() {
+        let state = get_mock_state().await;
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+        for (key, config) in [
+            (
+                format!("auto_retry_budget_{merchant_id}"),
+                r#"{"max_retries":2,"window_in_secs":60}"#,
+            ),
+            (format!("auto_retry_backoff_{merchant_id}"), "not json"),
+        ] {
+            state
+                .store
+                .insert_config(storage::ConfigNew {
+                    key,
+                    config: config.to_string(),
+                })
+                .await
+                .expect("failed to insert config");
+        }
+
+        let budget = get_retry_budget_config(&state, &merchant_id).await;
+        let backoff = get_retry_backoff_config(&state, &merchant_id).await;
+
+        assert_eq!((budget.max_retries, budget.window_in_secs), (2, 60));
+        assert_eq!(
+            (backoff.base_delay_in_millis, backoff.max_delay_in_millis),
+            (0, 0)
+        );
+    }
+
+    #[tokio::test]
+    #[allow(clippy::expect_used)]
+    async fn test_auto_retry_waits_for_backoff_only_between_attempts() {
+        let state = get_mock_state().await;
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+        state
+            .store
+            .insert_config(storage::ConfigNew {
+                key: format!("auto_retry_backoff_{merchant_id}"),
+                config: r#"{"base_delay_in_millis":100,"max_delay_in_millis":1000}"#.to_string(),
+            })
+            .await
+            .expect("failed to insert config");
+        let (mut retry_budget, mut retry_backoff) = (None, None);
+        tokio::time::pause();
+
+        let start = tokio::time::Instant::now();
+        let first_attempt = prepare_auto_retry(
+            &state,
+            &merchant_id,
+            0,
+            &mut retry_budget,
+            &mut retry_backoff,
+        )
+        .await;
+        let first_attempt_delay = start.elapsed();
+
+        let start = tokio::time::Instant::now();
+        let second_attempt = prepare_auto_retry(
+            &state,
+            &merchant_id,
+            1,
+            &mut retry_budget,
+            &mut retry_backoff,
+        )
+        .await;
+        let second_attempt_delay = start.elapsed();
+
+        assert_eq!(first_attempt, Ok(()));
+        assert_eq!(first_attempt_delay, Duration::ZERO);
+        assert_eq!(second_attempt, Ok(()));
+        assert!(second_attempt_delay >= Duration::from_millis(50));
+        assert!(second_attempt_delay <= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::expect_used)]
+    async fn test_auto_retry_stops_once_budget_is_exhausted() {
+        let state = get_mock_state().await;
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+        state
+            .store
+            .insert_config(storage::ConfigNew {
+                key: format!("auto_retry_budget_{merchant_id}"),
+                config: r#"{"max_retries":2,"window_in_secs":60}"#.to_string(),
+            })
+            .await
+            .expect("failed to insert config");
+
+        let mut retry_budget = None;
+        let mut outcomes = Vec::new();
+        for _ in 0..3 {
+            outcomes.push(
+                prepare_auto_retry(&state, &merchant_id, 1, &mut retry_budget, &mut None).await,
+            );
+        }
+
+        assert_eq!(
+            outcomes,
+            vec![Ok(()), Ok(()), Err(AutoRetryStopReason::BudgetExhausted)]
+        );
+        assert!(std::ptr::eq(
+            AutoRetryStopReason::BudgetExhausted.metric(),
+            &*metrics::AUTO_RETRY_BUDGET_EXHAUSTED_COUNT
+        ));
+        assert!(std::ptr::eq(
+            AutoRetryStopReason::RetriesExhausted.metric(),
+            &*metrics::AUTO_RETRY_EXHAUSTED_COUNT
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_remaining_retries_with_retries_enabled() {
+        let state = get_mock_state().await;
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+        #[allow(clippy::expect_used)]
+        state
+            .store
+            .insert_config(storage::ConfigNew {
+                key: format!("max_auto_retries_enabled_{merchant_id}"),
+                config: "3".to_string(),
+            })
+            .await
+            .expect("failed to insert retries config");
+
+        assert_eq!(remaining_retries(&state, &merchant_id, 1).await, Some(2));
+        assert_eq!(remaining_retries(&state, &merchant_id, 5).await, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_remaining_retries_with_retries_disabled() {
+        let state = get_mock_state().await;
+        let merchant_id = common_utils::generate_id_with_default_len("merchant");
+
+        assert_eq!(remaining_retries(&state, &merchant_id, 1).await, None);
+    }
+}
@@ -114,7 +114,6 @@ impl_misc_api_event_type!(
     Config,
     CreateFileRequest,
     FileId,
-    AttachEvidenceRequest,
     PaymentLinkFormData,
     ConfigUpdate
 );
@@ -151,6 +150,16 @@ impl ApiEventMetric for DisputeId {
     }
 }
 
+impl ApiEventMetric for AttachEvidenceRequest {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        self.create_file_request
+            .dispute_id
+            .clone()
+            .map(|dispute_id| ApiEventsType::Dispute { dispute_id })
+            .or(Some(ApiEventsType::Miscellaneous))
+    }
+}
+
 impl ApiEventMetric for PollId {
     fn get_api_event_type(&self) -> Option<ApiEventsType> {
         Some(ApiEventsType::Poll {
@@ -158,3 +167,45 @@ impl ApiEventMetric for PollId {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::api::{disputes::EvidenceType, files::FilePurpose};
+
+    fn get_attach_evidence_request(dispute_id: Option<String>) -> AttachEvidenceRequest {
+        AttachEvidenceRequest {
+            create_file_request: CreateFileRequest {
+                file: vec![],
+                file_name: Some("receipt.pdf".to_string()),
+                file_size: 0,
+                file_type: mime::APPLICATION_PDF,
+                purpose: FilePurpose::DisputeEvidence,
+                dispute_id,
+            },
+            evidence_type: EvidenceType::Receipt,
+        }
+    }
+
+    #[test]
+    fn test_attach_evidence_request_event_type_contains_dispute_id() {
+        let request = get_attach_evidence_request(Some("dp_123".to_string()));
+
+        assert_eq!(
+            request.get_api_event_type(),
+            Some(ApiEventsType::Dispute {
+                dispute_id: "dp_123".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_attach_evidence_request_event_type_without_dispute_id() {
+        let request = get_attach_evidence_request(None);
+
+        assert_eq!(
+            request.get_api_event_type(),
+            Some(ApiEventsType::Miscellaneous)
+        );
+    }
+}
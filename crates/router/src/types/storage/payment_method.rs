@@ -4,13 +4,23 @@ use std::{
 };
 
 use api_models::payment_methods;
+use common_utils::{
+    errors::{CustomResult, ParsingError},
+    ext_traits::{Encode, ValueExt},
+};
 use diesel_models::enums;
 pub use diesel_models::payment_method::{
     PaymentMethod, PaymentMethodNew, PaymentMethodUpdate, PaymentMethodUpdateInternal,
     TokenizeCoreWorkflow,
 };
 
-use crate::types::api::{self, payments};
+use crate::{
+    connector::utils::CardData,
+    types::{
+        api::{self, payments},
+        domain,
+    },
+};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -86,7 +96,7 @@ pub struct PaymentMethodListContext {
     pub bank_transfer_details: Option<api::BankPayout>,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PaymentsMandateReferenceRecord {
     pub connector_mandate_id: String,
     pub payment_method_type: Option<common_enums::PaymentMethodType>,
@@ -94,7 +104,8 @@ pub struct PaymentsMandateReferenceRecord {
     pub original_payment_authorized_currency: Option<common_enums::Currency>,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// Connector mandate details of a payment method, keyed by the merchant connector id
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PaymentsMandateReference(pub HashMap<String, PaymentsMandateReferenceRecord>);
 
 impl Deref for PaymentsMandateReference {
@@ -110,3 +121,176 @@ impl DerefMut for PaymentsMandateReference {
         &mut self.0
     }
 }
+
+pub trait PaymentMethodExt {
+    /// Parse the connector mandate details, returning `None` if they are absent
+    fn get_connector_mandate_details(
+        &self,
+    ) -> CustomResult<Option<PaymentsMandateReference>, ParsingError>;
+
+    fn set_connector_mandate_details(
+        &mut self,
+        connector_mandate_details: &PaymentsMandateReference,
+    ) -> CustomResult<(), ParsingError>;
+}
+
+impl PaymentMethodExt for PaymentMethod {
+    fn get_connector_mandate_details(
+        &self,
+    ) -> CustomResult<Option<PaymentsMandateReference>, ParsingError> {
+        self.connector_mandate_details
+            .clone()
+            .map(|details| details.parse_value("PaymentsMandateReference"))
+            .transpose()
+    }
+
+    fn set_connector_mandate_details(
+        &mut self,
+        connector_mandate_details: &PaymentsMandateReference,
+    ) -> CustomResult<(), ParsingError> {
+        self.connector_mandate_details = Some(connector_mandate_details.encode_to_value()?);
+        Ok(())
+    }
+}
+
+pub trait PaymentMethodNewExt {
+    /// Build a card payment method with the card issuer and scheme derived from the card, leaving
+    /// the encrypted payment method data and the locker id to be set by the caller
+    fn from_card(
+        card: &domain::Card,
+        customer_id: &str,
+        merchant_id: &str,
+        payment_method_id: &str,
+    ) -> Self;
+}
+
+impl PaymentMethodNewExt for PaymentMethodNew {
+    fn from_card(
+        card: &domain::Card,
+        customer_id: &str,
+        merchant_id: &str,
+        payment_method_id: &str,
+    ) -> Self {
+        let derived_card_issuer = card
+            .get_card_issuer()
+            .ok()
+            .map(|card_issuer| card_issuer.to_string());
+        let now = common_utils::date_time::now();
+
+        Self {
+            customer_id: customer_id.to_string(),
+            merchant_id: merchant_id.to_string(),
+            payment_method_id: payment_method_id.to_string(),
+            payment_method: Some(enums::PaymentMethod::Card),
+            payment_method_issuer: card
+                .card_issuer
+                .clone()
+                .or_else(|| derived_card_issuer.clone()),
+            scheme: card
+                .card_network
+                .as_ref()
+                .map(ToString::to_string)
+                .or(derived_card_issuer),
+            issuer_country: card.card_issuing_country.clone(),
+            created_at: now,
+            last_modified: now,
+            last_used_at: now,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn get_mandate_reference_record(connector_mandate_id: &str) -> PaymentsMandateReferenceRecord {
+        PaymentsMandateReferenceRecord {
+            connector_mandate_id: connector_mandate_id.to_string(),
+            payment_method_type: Some(enums::PaymentMethodType::Credit),
+            original_payment_authorized_amount: Some(1000),
+            original_payment_authorized_currency: Some(enums::Currency::USD),
+        }
+    }
+
+    #[test]
+    fn test_connector_mandate_details_round_trip() {
+        let mut payment_method = PaymentMethod::from(&PaymentMethodNew::default());
+        assert_eq!(
+            payment_method.get_connector_mandate_details().unwrap(),
+            None
+        );
+
+        let connector_mandate_details = PaymentsMandateReference(HashMap::from([
+            (
+                "mca_1".to_string(),
+                get_mandate_reference_record("mandate_1"),
+            ),
+            (
+                "mca_2".to_string(),
+                get_mandate_reference_record("mandate_2"),
+            ),
+        ]));
+        payment_method
+            .set_connector_mandate_details(&connector_mandate_details)
+            .unwrap();
+
+        assert_eq!(
+            payment_method.connector_mandate_details.as_ref().unwrap()["mca_2"]
+                ["connector_mandate_id"],
+            "mandate_2"
+        );
+        assert_eq!(
+            payment_method.get_connector_mandate_details().unwrap(),
+            Some(connector_mandate_details)
+        );
+    }
+
+    #[test]
+    fn test_connector_mandate_details_malformed() {
+        let payment_method = PaymentMethod::from(&PaymentMethodNew {
+            connector_mandate_details: Some(serde_json::json!({ "mca_1": "mandate_1" })),
+            ..PaymentMethodNew::default()
+        });
+
+        assert!(payment_method.get_connector_mandate_details().is_err());
+    }
+
+    #[test]
+    fn test_payment_method_new_from_visa_card() {
+        let card = domain::Card {
+            card_number: cards::CardNumber::from_str("4111111111111111").unwrap(),
+            card_exp_month: masking::Secret::new("10".to_string()),
+            card_exp_year: masking::Secret::new("2030".to_string()),
+            card_cvc: masking::Secret::new("123".to_string()),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: Some("US".to_string()),
+            bank_code: None,
+            nick_name: None,
+        };
+
+        let payment_method_new = PaymentMethodNew::from_card(&card, "cus_1", "merchant_1", "pm_1");
+
+        assert_eq!(payment_method_new.customer_id, "cus_1");
+        assert_eq!(payment_method_new.merchant_id, "merchant_1");
+        assert_eq!(payment_method_new.payment_method_id, "pm_1");
+        assert_eq!(
+            payment_method_new.payment_method,
+            Some(enums::PaymentMethod::Card)
+        );
+        assert_eq!(
+            payment_method_new.payment_method_issuer.as_deref(),
+            Some("Visa")
+        );
+        assert_eq!(payment_method_new.scheme.as_deref(), Some("Visa"));
+        assert_eq!(payment_method_new.issuer_country.as_deref(), Some("US"));
+        assert!(payment_method_new.payment_method_data.is_none());
+        assert!(payment_method_new.locker_id.is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use api_models::enums::{AuthenticationType, Connector, PaymentMethod, PaymentMethodType};
 use common_utils::errors::CustomResult;
 use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
 use hyperswitch_domain_models::{
     errors::StorageError,
     payments::payment_attempt::{
@@ -52,12 +53,25 @@ impl PaymentAttemptInterface for MockDb {
 
     async fn find_payment_attempt_by_attempt_id_merchant_id(
         &self,
-        _attempt_id: &str,
-        _merchant_id: &str,
+        attempt_id: &str,
+        merchant_id: &str,
         _storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        payment_attempts
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.attempt_id == attempt_id
+                    && payment_attempt.merchant_id == merchant_id
+            })
+            .cloned()
+            .ok_or_else(|| {
+                StorageError::ValueNotFound(format!(
+                    "cannot find payment attempt for attempt_id = {attempt_id}"
+                ))
+                .into()
+            })
     }
 
     async fn find_payment_attempt_by_preprocessing_id_merchant_id(
@@ -100,7 +114,9 @@ impl PaymentAttemptInterface for MockDb {
         #[allow(clippy::as_conversions)]
         let id = payment_attempts.len() as i32;
         let time = common_utils::date_time::now();
-        let payment_attempt = payment_attempt.populate_derived_fields();
+        let payment_attempt = payment_attempt
+            .populate_derived_fields()
+            .change_context(StorageError::MockDbError)?;
         let payment_attempt = PaymentAttempt {
             id,
             payment_id: payment_attempt.payment_id,
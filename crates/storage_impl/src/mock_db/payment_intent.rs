@@ -63,6 +63,15 @@ impl PaymentIntentInterface for MockDb {
         storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> CustomResult<PaymentIntent, StorageError> {
         let mut payment_intents = self.payment_intents.lock().await;
+        if payment_intents.iter().any(|payment_intent| {
+            payment_intent.payment_id == new.payment_id
+                && payment_intent.merchant_id == new.merchant_id
+        }) {
+            Err(StorageError::DuplicateValue {
+                entity: "payment_intent",
+                key: Some(new.payment_id.clone()),
+            })?
+        }
         let time = common_utils::date_time::now();
         let payment_intent = PaymentIntent {
             #[allow(clippy::as_conversions)]
@@ -113,8 +122,6 @@ impl PaymentIntentInterface for MockDb {
         Ok(payment_intent)
     }
 
-    // safety: only used for testing
-    #[allow(clippy::unwrap_used)]
     async fn update_payment_intent(
         &self,
         this: PaymentIntent,
@@ -125,7 +132,10 @@ impl PaymentIntentInterface for MockDb {
         let payment_intent = payment_intents
             .iter_mut()
             .find(|item| item.id == this.id)
-            .unwrap();
+            .ok_or(StorageError::ValueNotFound(format!(
+                "cannot find payment intent for payment_id = {}",
+                this.payment_id
+            )))?;
         *payment_intent = PaymentIntent::from_storage_model(
             update
                 .to_storage_model()
@@ -134,8 +144,6 @@ impl PaymentIntentInterface for MockDb {
         Ok(payment_intent.clone())
     }
 
-    // safety: only used for testing
-    #[allow(clippy::unwrap_used)]
     async fn find_payment_intent_by_payment_id_merchant_id(
         &self,
         payment_id: &str,
@@ -144,13 +152,18 @@ impl PaymentIntentInterface for MockDb {
     ) -> CustomResult<PaymentIntent, StorageError> {
         let payment_intents = self.payment_intents.lock().await;
 
-        Ok(payment_intents
+        payment_intents
             .iter()
             .find(|payment_intent| {
                 payment_intent.payment_id == payment_id && payment_intent.merchant_id == merchant_id
             })
             .cloned()
-            .unwrap())
+            .ok_or(
+                StorageError::ValueNotFound(format!(
+                    "cannot find payment intent for payment_id = {payment_id}, merchant_id = {merchant_id}"
+                ))
+                .into(),
+            )
     }
 
     async fn get_active_payment_attempt(
@@ -173,3 +186,175 @@ impl PaymentIntentInterface for MockDb {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use common_utils::pii;
+    use diesel_models::enums as storage_enums;
+    use hyperswitch_domain_models::{
+        errors::StorageError,
+        payments::payment_intent::{PaymentIntentInterface, PaymentIntentNew, PaymentIntentUpdate},
+        RemoteStorageObject,
+    };
+    use redis_interface::RedisSettings;
+
+    use crate::mock_db::MockDb;
+
+    fn create_payment_intent_new(payment_id: &str, merchant_id: &str) -> PaymentIntentNew {
+        PaymentIntentNew {
+            payment_id: payment_id.to_string(),
+            merchant_id: merchant_id.to_string(),
+            status: storage_enums::IntentStatus::RequiresPaymentMethod,
+            amount: 1000,
+            currency: Some(storage_enums::Currency::USD),
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: None,
+            modified_at: None,
+            last_synced: None,
+            setup_future_usage: None,
+            off_session: None,
+            client_secret: None,
+            active_attempt: RemoteStorageObject::ForeignID(format!("{payment_id}_1")),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            profile_id: None,
+            merchant_decision: None,
+            payment_link_id: None,
+            payment_confirm_source: None,
+            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            surcharge_applicable: None,
+            request_incremental_authorization: None,
+            incremental_authorization_allowed: None,
+            authorization_count: None,
+            fingerprint_id: None,
+            session_expiry: None,
+            request_external_three_ds_authentication: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_find_payment_intent() {
+        let mockdb = MockDb::new(&RedisSettings::default())
+            .await
+            .expect("Failed to create a mock DB");
+
+        let created_payment_intent = mockdb
+            .insert_payment_intent(
+                create_payment_intent_new("pay_1", "merchant_1"),
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let found_payment_intent = mockdb
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(created_payment_intent, found_payment_intent);
+
+        let not_found = mockdb
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_2",
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            not_found.current_context(),
+            StorageError::ValueNotFound(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_insert_duplicate_payment_intent() {
+        let mockdb = MockDb::new(&RedisSettings::default())
+            .await
+            .expect("Failed to create a mock DB");
+
+        mockdb
+            .insert_payment_intent(
+                create_payment_intent_new("pay_1", "merchant_1"),
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let duplicate = mockdb
+            .insert_payment_intent(
+                create_payment_intent_new("pay_1", "merchant_1"),
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            duplicate.current_context(),
+            StorageError::DuplicateValue { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_payment_intent() {
+        let mockdb = MockDb::new(&RedisSettings::default())
+            .await
+            .expect("Failed to create a mock DB");
+
+        let created_payment_intent = mockdb
+            .insert_payment_intent(
+                create_payment_intent_new("pay_1", "merchant_1"),
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let metadata = pii::SecretSerdeValue::new(serde_json::json!({ "order_id": "order_1" }));
+        let updated_payment_intent = mockdb
+            .update_payment_intent(
+                created_payment_intent.clone(),
+                PaymentIntentUpdate::MetadataUpdate {
+                    metadata: metadata.clone(),
+                    updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+                },
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated_payment_intent.metadata, Some(metadata));
+        assert_eq!(updated_payment_intent.amount, created_payment_intent.amount);
+        assert_eq!(updated_payment_intent.status, created_payment_intent.status);
+
+        let found_payment_intent = mockdb
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(found_payment_intent, updated_payment_intent);
+    }
+}
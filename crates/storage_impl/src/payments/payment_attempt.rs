@@ -340,7 +340,10 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     .await
             }
             MerchantStorageScheme::RedisKv => {
-                let payment_attempt = payment_attempt.populate_derived_fields();
+                let payment_attempt = payment_attempt
+                    .populate_derived_fields()
+                    .change_context(errors::StorageError::KVError)
+                    .attach_printable("Failed to derive the net amount of the payment attempt")?;
                 let merchant_id = payment_attempt.merchant_id.clone();
                 let payment_id = payment_attempt.payment_id.clone();
                 let key = PartitionKey::MerchantIdPaymentId {
@@ -24,10 +24,7 @@ impl PubSubInterface for redis_interface::RedisConnectionPool {
         // Spawns a task that will automatically re-subscribe to any channels or channel patterns used by the client.
         self.subscriber.manage_subscriptions();
 
-        self.subscriber
-            .subscribe(channel)
-            .await
-            .change_context(redis_errors::RedisError::SubscribeError)
+        self.subscribe_to_channel(channel).await
     }
 
     #[inline]
@@ -37,7 +34,7 @@ impl PubSubInterface for redis_interface::RedisConnectionPool {
         key: CacheKind<'a>,
     ) -> error_stack::Result<usize, redis_errors::RedisError> {
         self.publisher
-            .publish(channel, RedisValue::from(key).into_inner())
+            .publish(self.add_prefix(channel), RedisValue::from(key).into_inner())
             .await
             .change_context(redis_errors::RedisError::SubscribeError)
     }